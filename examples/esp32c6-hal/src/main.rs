@@ -103,8 +103,10 @@ async fn main(_spawner: Spawner) {
     log::info!("Radar calibrated and prepared.");
 
     let mut distance_config = RadarDistanceConfig::default();
-    distance_config.set_interval(0.2..=3.0);
-    distance_config.set_max_step_length(MaxStepLenght::ProfileBased);
+    distance_config.set_interval(0.2..=3.0).unwrap();
+    distance_config
+        .set_max_step_length(MaxStepLenght::ProfileBased)
+        .unwrap();
     distance_config.set_max_profile(AccProfile5);
     distance_config.set_reflector_shape(ReflectorShape::Generic);
     distance_config.set_peak_sorting_method(PeakSortingMethod::Strength);
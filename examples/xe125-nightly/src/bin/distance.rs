@@ -55,7 +55,7 @@ async fn main(_spawner: Spawner) {
     let mut radar = radar.prepare_sensor(&mut calibration).unwrap();
 
     let mut dist_config = RadarDistanceConfig::balanced();
-    dist_config.set_interval(4.0..=5.5);
+    dist_config.set_interval(4.0..=5.5).unwrap();
     let mut distance = RadarDistanceDetector::with_config(&mut radar, dist_config);
     let mut buffer = vec![0u8; distance.get_distance_buffer_size()];
     let mut static_cal_result = vec![0u8; distance.get_static_result_buffer_size()];
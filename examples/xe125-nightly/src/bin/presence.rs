@@ -0,0 +1,82 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec;
+use core::cell::RefCell;
+
+use defmt::{debug, info, warn};
+use embassy_executor::Spawner;
+use embassy_stm32::exti::ExtiInput;
+use embassy_stm32::gpio::{Input, Level, Output, Pull, Speed};
+use embassy_stm32::spi::Spi;
+use embassy_time::Delay;
+use embedded_hal_bus::spi::ExclusiveDevice;
+
+use a121_rs::detector::presence::config::PresenceConfig;
+use a121_rs::detector::presence::results::ProcessDataError;
+use a121_rs::detector::presence::session::PresenceSession;
+use a121_rs::detector::presence::PresenceDetector;
+use a121_rs::radar;
+use a121_rs::radar::Radar;
+use radar::rss_version;
+use xe125_nightly::adapter::SpiAdapter;
+use xe125_nightly::*;
+
+#[embassy_executor::main]
+async fn main(_spawner: Spawner) {
+    let p = embassy_stm32::init(xm125_clock_config());
+
+    let enable = Output::new(p.PB12, Level::Low, Speed::VeryHigh); // ENABLE on PB12
+    let cs_pin = Output::new(p.PB0, Level::High, Speed::VeryHigh);
+    let input = Input::new(p.PB3, Pull::Up);
+    let interrupt = ExtiInput::new(input, p.EXTI3); // INTERRUPT on PB3 used as 'ready' signal
+    info!("GPIO initialized.");
+
+    let spi = Spi::new(
+        p.SPI1,
+        p.PA5, // SCK
+        p.PA7, // MOSI
+        p.PA6, // MISO
+        p.DMA2_CH3,
+        p.DMA2_CH2,
+        xm125_spi_config(),
+    );
+    let exclusive_device = ExclusiveDevice::new(spi, cs_pin, Delay);
+
+    unsafe { SPI_DEVICE = Some(RefCell::new(SpiAdapter::new(exclusive_device))) };
+    let spi_mut_ref = unsafe { SPI_DEVICE.as_mut().unwrap() };
+
+    debug!("RSS Version: {}", rss_version());
+
+    let mut radar = Radar::new(1, spi_mut_ref.get_mut(), interrupt, enable, Delay).await;
+    info!("Radar enabled.");
+    let mut calibration = radar.calibrate().await.unwrap();
+    info!("Calibration complete.");
+    let mut radar = radar.prepare_sensor(&mut calibration).unwrap();
+
+    let mut presence_config = PresenceConfig::default();
+    presence_config.set_range(0.3..=2.5);
+    let presence = PresenceDetector::with_config(&mut radar, presence_config);
+    let mut session = PresenceSession::new(presence, calibration);
+    let mut buffer = vec![0u8; session.buffer_size()];
+
+    loop {
+        match session.detect_once(&mut buffer).await {
+            Ok(res) => {
+                if res.presence_detected {
+                    info!(
+                        "Presence detected at {} m (intra: {}, inter: {})",
+                        res.presence_distance, res.intra_presence_score, res.inter_presence_score
+                    );
+                }
+            }
+            Err(ProcessDataError::CalibrationNeeded) => {
+                info!("Calibration needed.");
+                session.recalibrate().await.unwrap();
+            }
+            Err(_) => warn!("Failed to detect presence."),
+        }
+    }
+}
@@ -1,7 +1,10 @@
 #![warn(missing_docs)]
 
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
 use core::num::NonZeroU8;
-use defmt::trace;
+use core::ops::Deref;
+use defmt::{trace, warn};
 
 use num::Zero;
 
@@ -10,13 +13,22 @@ use error::ConfigError::ContinuousSweepMode;
 use frame_rate::FrameRate;
 use profile::RadarProfile;
 
+use crate::config::diff::{ConfigDiff, ConfigField};
+use crate::config::fingerprint::Fingerprint;
 use crate::config::hwaas::Hwaas;
 use crate::config::prf::PulseRepetitionFrequency;
-use crate::config::subsweep::Subsweep;
+use crate::config::subsweep::{Subsweep, SubsweepConfigurator};
+use crate::num::Points;
 use a121_sys::*;
 
+/// Module for radar configuration comparison
+pub mod diff;
 /// Module for radar configuration errors
-mod error;
+pub mod error;
+/// Module for loading radar configurations from files
+#[cfg(feature = "serde")]
+pub mod file;
+pub(crate) mod fingerprint;
 /// Module for frame rate values
 pub mod frame_rate;
 /// Module for hardware accelerated average samples (HWAAS) values
@@ -28,7 +40,33 @@ pub mod profile;
 /// Module for subsweep configuration
 pub mod subsweep;
 
-#[derive(Debug, PartialEq)]
+/// Maximum total number of points the sensor can measure across all subsweeps of a sweep.
+///
+/// This is bounded by the size of the sensor's internal buffer (4095 complex samples).
+pub const MAX_TOTAL_POINTS: u32 = 4095;
+
+/// Minimum size, in bytes, of the buffer used for sensor calibration.
+pub const MIN_CALIBRATION_BUFFER_SIZE: usize = 5560;
+
+/// Size of a calibration buffer chosen by [`RadarConfig::calibration_buffer_plan`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub struct CalibrationBufferPlan {
+    /// Size of the buffer, in bytes
+    pub size: usize,
+    /// Expected number of buffer-sized transfers between the host and the sensor
+    pub transactions: usize,
+}
+
+/// Highest receiver gain setting.
+pub const MAX_RECEIVER_GAIN: u8 = 23;
+
+/// Maximum number of complex samples in a frame, bounded by the sensor's internal buffer.
+///
+/// With double buffering enabled, the buffer is split in two halves and only half of this is
+/// available for a frame.
+pub const MAX_FRAME_SAMPLES: u32 = 4095;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 /// Idle states for the radar sensor between sweeps or frames.
 pub enum RadarIdleState {
     /// Deep sleep state for maximum power saving.
@@ -62,6 +100,25 @@ pub struct RadarConfig {
     num_subsweep: Option<NonZeroU8>,
     /// Internal pointer to the radar configuration.
     inner: *mut acc_config_t,
+    /// Incremented on each mutation, see [`generation`](RadarConfig::generation).
+    generation: u32,
+}
+
+/// Non-owning view of a radar configuration owned elsewhere, e.g. by the SDK.
+///
+/// Gives access to the getters of [`RadarConfig`] without destroying the configuration when
+/// dropped.
+pub struct RadarConfigRef<'a> {
+    config: ManuallyDrop<RadarConfig>,
+    _marker: PhantomData<&'a acc_config_t>,
+}
+
+impl Deref for RadarConfigRef<'_> {
+    type Target = RadarConfig;
+
+    fn deref(&self) -> &Self::Target {
+        &self.config
+    }
 }
 
 impl Default for RadarConfig {
@@ -78,6 +135,13 @@ impl Drop for RadarConfig {
     }
 }
 
+impl PartialEq for RadarConfig {
+    /// Compares two configurations by their settings, including all subsweeps.
+    fn eq(&self, other: &Self) -> bool {
+        self.diff(other).is_empty()
+    }
+}
+
 impl RadarConfig {
     /// Creates a new radar configuration instance with a specified ID.
     pub fn new() -> Self {
@@ -87,21 +151,88 @@ impl RadarConfig {
         Self {
             inner,
             num_subsweep: None,
+            generation: 0,
         }
     }
 
+    /// Returns the generation of the configuration, incremented each time it is mutated.
+    ///
+    /// Comparing generations tells whether a configuration changed since it was last used,
+    /// e.g. by a detector.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Creates a configuration without an SDK instance, which must never be dropped.
+    #[cfg(test)]
+    pub(crate) fn detached() -> ManuallyDrop<Self> {
+        ManuallyDrop::new(Self {
+            num_subsweep: None,
+            inner: core::ptr::null_mut(),
+            generation: 0,
+        })
+    }
+
+    fn bump_generation(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    /// Returns the inner configuration for an infallible subsweep setter, bumping the generation.
+    fn inner_mut(&mut self) -> *mut acc_config_t {
+        self.bump_generation();
+        self.inner
+    }
+
+    /// Resets every setting, including the subsweeps, to the SDK defaults.
+    ///
+    /// The inner configuration is destroyed and created again, so pointers previously
+    /// obtained with [`ptr`](Self::ptr) or [`mut_ptr`](Self::mut_ptr) are invalidated.
+    pub fn reset_to_default(&mut self) {
+        let inner = unsafe { acc_config_create() };
+        unsafe { acc_config_destroy(self.inner) };
+        self.inner = inner;
+        self.num_subsweep = None;
+        self.bump_generation();
+    }
+
     /// Returns a mutable pointer to the internal radar configuration structure
+    ///
+    /// Changes made through the pointer are not tracked by the [`generation`](Self::generation),
+    /// call [`mark_changed`](Self::mark_changed) after modifying the configuration.
     /// # Safety
     /// This function is unsafe because it returns a raw pointer.
     pub unsafe fn mut_ptr(&mut self) -> *mut acc_config_t {
         self.inner
     }
 
+    /// Records a change made through [`mut_ptr`](Self::mut_ptr) by bumping the
+    /// [`generation`](Self::generation).
+    pub fn mark_changed(&mut self) {
+        self.bump_generation();
+    }
+
     /// Returns a pointer to the internal radar configuration structure
     pub fn ptr(&self) -> *const acc_config_t {
         self.inner
     }
 
+    /// Creates a read-only view of a configuration owned elsewhere.
+    ///
+    /// The configuration is not destroyed when the view is dropped.
+    /// # Safety
+    /// `ptr` must point to a valid configuration that outlives the returned view and is not
+    /// modified while the view exists.
+    pub unsafe fn from_raw_borrowed<'a>(ptr: *const acc_config_t) -> RadarConfigRef<'a> {
+        RadarConfigRef {
+            config: ManuallyDrop::new(Self {
+                num_subsweep: None,
+                inner: ptr as *mut acc_config_t,
+                generation: 0,
+            }),
+            _marker: PhantomData,
+        }
+    }
+
     /// Sets the sweep mode for the radar sensor
     /// # Arguments
     /// * `sweep_mode` - The sweep mode to set
@@ -127,9 +258,10 @@ impl RadarConfig {
     ///
     /// # Arguments
     ///
-    /// * `start_point` - The starting point of the sweep in millimeters.
+    /// * `start_point` - The starting point of the sweep in points of 2.5 mm.
     pub fn set_start_point(&mut self, start_point: i32) {
         unsafe { acc_config_start_point_set(self.inner, start_point) };
+        self.bump_generation();
     }
 
     /// Get the starting point of the sweep.
@@ -137,6 +269,27 @@ impl RadarConfig {
         unsafe { acc_config_start_point_get(self.inner) }
     }
 
+    /// Sets the starting point of the sweep from a distance, converted to points by the SDK.
+    ///
+    /// # Arguments
+    ///
+    /// * `meters` - The distance to the start of the sweep in meters.
+    ///
+    /// # Returns
+    /// `Err(ConfigError::StartPoint)` if `meters` is negative or not a number.
+    pub fn set_start_distance_m(&mut self, meters: f32) -> Result<(), ConfigError> {
+        if !meters.is_finite() || meters < 0.0 {
+            return Err(ConfigError::StartPoint);
+        }
+        self.set_start_point(Points::meters_to_points(meters).points);
+        Ok(())
+    }
+
+    /// Get the starting point of the sweep in meters.
+    pub fn start_distance_m(&self) -> f32 {
+        Points::new(self.start_point()).to_meters()
+    }
+
     /// Set the number of data points to measure in a sweep.
     ///
     /// # Arguments
@@ -144,6 +297,7 @@ impl RadarConfig {
     /// * `num_points` - Number of data points to measure.
     pub fn set_num_points(&mut self, num_points: u16) {
         unsafe { acc_config_num_points_set(self.inner, num_points) };
+        self.bump_generation();
     }
 
     /// Get the number of data points set to measure in a sweep.
@@ -158,6 +312,7 @@ impl RadarConfig {
     /// * `step_length` - The step length.
     pub fn set_step_length(&mut self, step_length: u16) {
         unsafe { acc_config_step_length_set(self.inner, step_length) };
+        self.bump_generation();
     }
 
     /// Get the current step length between each data point in a sweep.
@@ -167,11 +322,20 @@ impl RadarConfig {
 
     /// Set the radar profile.
     ///
+    /// The 19.5 MHz PRF is only available with profile 1. When switching to another profile
+    /// while it is set, the PRF is lowered to 15.6 MHz and a warning is logged, instead of
+    /// leaving a configuration that would only fail when preparing the sensor.
+    ///
     /// # Arguments
     ///
     /// * `profile` - The radar profile to set.
     pub fn set_profile(&mut self, profile: RadarProfile) {
-        unsafe { acc_config_profile_set(self.inner, profile as u32) };
+        unsafe { acc_config_profile_set(self.inner, profile.to_ffi()) };
+        self.bump_generation();
+        if !self.prf().is_supported_by(profile) {
+            warn!("19.5 MHz PRF requires profile 1, using 15.6 MHz");
+            self.set_prf(PulseRepetitionFrequency::Prf15_6Mhz);
+        }
     }
 
     /// Get the currently used radar profile
@@ -190,8 +354,16 @@ impl RadarConfig {
     /// # Arguments
     ///
     /// * `hwaas` - Number of hardware accelerated average samples.
+    ///
+    /// # Returns
+    /// `Err(ConfigError::Hwaas)` if `hwaas` is out of range, in which case the configuration
+    /// is left unchanged.
     pub fn set_hwaas(&mut self, hwaas: Hwaas) -> Result<(), ConfigError> {
+        if !hwaas.is_valid() {
+            return Err(ConfigError::Hwaas);
+        }
         unsafe { acc_config_hwaas_set(self.inner, hwaas.into()) };
+        self.bump_generation();
         Ok(())
     }
 
@@ -215,6 +387,26 @@ impl RadarConfig {
     /// * `receiver_gain` - Receiver gain setting.
     pub fn receiver_gain_set(&mut self, receiver_gain: u8) {
         unsafe { acc_config_receiver_gain_set(self.inner, receiver_gain) };
+        self.bump_generation();
+    }
+
+    /// Set the receiver gain of every subsweep.
+    ///
+    /// The top-level settings such as [`receiver_gain_set`](Self::receiver_gain_set) only apply
+    /// to the first subsweep, so with several subsweeps the gain must be set for each of them,
+    /// which this does.
+    ///
+    /// # Returns
+    /// `Err(ConfigError::ReceiverGain)` if `gain` is greater than [`MAX_RECEIVER_GAIN`], in which
+    /// case no subsweep is changed.
+    pub fn set_all_subsweep_gain(&mut self, gain: u8) -> Result<(), ConfigError> {
+        if gain > MAX_RECEIVER_GAIN {
+            return Err(ConfigError::ReceiverGain);
+        }
+        for index in 0..self.num_subsweep() {
+            Subsweep::new(index).set_receiver_gain(self, gain);
+        }
+        Ok(())
     }
 
     /// Get the current receiver gain setting.
@@ -231,6 +423,7 @@ impl RadarConfig {
     /// * `sweeps_per_frame` - Number of sweeps per frame.
     pub fn set_sweeps_per_frame(&mut self, sweeps_per_frame: u16) {
         unsafe { acc_config_sweeps_per_frame_set(self.inner, sweeps_per_frame) };
+        self.bump_generation();
     }
 
     /// Get the number of sweeps captured in each frame (measurement).
@@ -247,6 +440,7 @@ impl RadarConfig {
     /// * `prf` - The Pulse Repetition Frequency to use
     pub fn set_prf(&mut self, prf: PulseRepetitionFrequency) {
         unsafe { acc_config_prf_set(self.inner, prf as acc_config_prf_t) };
+        self.bump_generation();
     }
 
     /// Get the Pulse Repetition Frequency
@@ -267,6 +461,7 @@ impl RadarConfig {
     /// * `enable` - true to enable phase enhancement, false to disable
     pub fn set_phase_enhancement(&mut self, enable: bool) {
         unsafe { acc_config_phase_enhancement_set(self.inner, enable) };
+        self.bump_generation();
     }
 
     /// Check if phase enhancement is enabled
@@ -285,6 +480,7 @@ impl RadarConfig {
     /// * `enable` - true to enable loopback, false otherwise
     pub fn set_loopback(&mut self, enable: bool) {
         unsafe { acc_config_enable_loopback_set(self.inner, enable) };
+        self.bump_generation();
     }
 
     /// Get the enable loopback configuration
@@ -299,11 +495,15 @@ impl RadarConfig {
     /// If enabled, the sensor buffer will be split in two halves reducing the
     /// maximum number of samples.
     ///
+    /// Double buffering cannot be combined with continuous sweep mode and requires more than
+    /// one sweep per frame, which [`validate`](Self::validate) checks.
+    ///
     /// # Arguments
     ///
     /// * `enable` - true to enable double buffering, false otherwise
     pub fn set_double_buffering(&mut self, enable: bool) {
         unsafe { acc_config_double_buffering_set(self.inner, enable) };
+        self.bump_generation();
     }
 
     /// Get the double buffering configuration
@@ -325,6 +525,7 @@ impl RadarConfig {
             FrameRate::Unlimited => unsafe { acc_config_frame_rate_set(self.inner, 0.0) },
             FrameRate::Limited(rate) => unsafe { acc_config_frame_rate_set(self.inner, rate) },
         }
+        self.bump_generation();
     }
 
     /// Get the frame rate
@@ -346,6 +547,7 @@ impl RadarConfig {
     /// * `enable` - true to enable the transmitter, false to disable it
     pub fn set_transmitter_enabled(&mut self, enable: bool) {
         unsafe { acc_config_enable_tx_set(self.inner, enable) };
+        self.bump_generation();
     }
 
     /// Get transmitter enable configuration
@@ -362,6 +564,7 @@ impl RadarConfig {
     /// * `idle_state` - The idle state to use between frames
     pub fn set_inter_frame_idle_state(&mut self, idle_state: RadarIdleState) {
         unsafe { acc_config_inter_frame_idle_state_set(self.inner, idle_state as u32) };
+        self.bump_generation();
     }
 
     /// Get inter frame idle state
@@ -384,6 +587,7 @@ impl RadarConfig {
     /// * `idle_state` - The idle state to use between sweeps within a frame
     pub fn set_inter_sweep_idle_state(&mut self, idle_state: RadarIdleState) {
         unsafe { acc_config_inter_sweep_idle_state_set(self.inner, idle_state as u32) };
+        self.bump_generation();
     }
 
     /// Get inter sweep idle state
@@ -423,6 +627,7 @@ impl RadarConfig {
             }
         }
         unsafe { acc_config_continuous_sweep_mode_set(self.inner, enabled) };
+        self.bump_generation();
         Ok(())
     }
 
@@ -443,6 +648,7 @@ impl RadarConfig {
             return Err(ConfigError::SweepRate);
         }
         unsafe { acc_config_sweep_rate_set(self.inner, sweep_rate) };
+        self.bump_generation();
         Ok(())
     }
 
@@ -462,6 +668,7 @@ impl RadarConfig {
         }
         unsafe { acc_config_num_subsweeps_set(self.inner, num_subsweep) };
         self.num_subsweep = NonZeroU8::new(num_subsweep);
+        self.bump_generation();
         Ok(())
     }
 
@@ -483,6 +690,312 @@ impl RadarConfig {
         Some(Subsweep::new(index))
     }
 
+    /// Get a chainable configurator for a subsweep of the radar configuration.
+    /// # Arguments
+    /// * `index` - The index of the subsweep to configure
+    /// # Returns
+    /// * `Some(SubsweepConfigurator)` - A configurator for the subsweep at the given index
+    /// * `None` - If the index is out of bounds
+    pub fn subsweep_mut(&mut self, index: u8) -> Option<SubsweepConfigurator<'_>> {
+        let subsweep = self.get_subsweep(index)?;
+        Some(SubsweepConfigurator::new(self, subsweep))
+    }
+
+    /// Returns an iterator over the subsweeps of the radar configuration.
+    pub fn subsweeps(&self) -> impl Iterator<Item = Subsweep> {
+        (0..self.num_subsweep()).map(Subsweep::new)
+    }
+
+    /// Returns the total number of points measured across all subsweeps.
+    pub fn total_num_points(&self) -> u32 {
+        self.subsweeps()
+            .map(|subsweep| u32::from(subsweep.num_points(self)))
+            .sum()
+    }
+
+    /// Lists the settings that differ between two configurations.
+    ///
+    /// Subsweep settings are only compared for the subsweeps present in both configurations.
+    pub fn diff(&self, other: &Self) -> ConfigDiff {
+        let mut diff = ConfigDiff::new();
+        diff.check(
+            ConfigField::NumSubsweeps,
+            self.num_subsweep() != other.num_subsweep(),
+        );
+        diff.check(
+            ConfigField::SweepsPerFrame,
+            self.sweeps_per_frame() != other.sweeps_per_frame(),
+        );
+        diff.check(
+            ConfigField::FrameRate,
+            self.frame_rate() != other.frame_rate(),
+        );
+        diff.check(
+            ConfigField::SweepRate,
+            self.sweep_rate() != other.sweep_rate(),
+        );
+        diff.check(
+            ConfigField::ContinuousSweepMode,
+            self.is_continuous_sweep_mode_enabled() != other.is_continuous_sweep_mode_enabled(),
+        );
+        diff.check(
+            ConfigField::DoubleBuffering,
+            self.is_double_buffering_enabled() != other.is_double_buffering_enabled(),
+        );
+        diff.check(
+            ConfigField::InterFrameIdleState,
+            self.inter_frame_idle_state() != other.inter_frame_idle_state(),
+        );
+        diff.check(
+            ConfigField::InterSweepIdleState,
+            self.inter_sweep_idle_state() != other.inter_sweep_idle_state(),
+        );
+        let num_subsweep = self.num_subsweep().min(other.num_subsweep());
+        (0..num_subsweep)
+            .map(|index| Subsweep::new(index).diff(self, other))
+            .fold(diff, |acc, subsweep_diff| acc | subsweep_diff)
+    }
+
+    /// Returns a fingerprint of all the settings of the configuration.
+    ///
+    /// Configurations with the same settings have the same fingerprint, which is stable across
+    /// runs and platforms, so it can be used as a key for cached calibrations.
+    pub fn fingerprint(&self) -> u64 {
+        let mut fingerprint = Fingerprint::new();
+        fingerprint
+            .u32(self.num_subsweep() as u32)
+            .u32(self.sweeps_per_frame() as u32)
+            .f32(self.frame_rate().value())
+            .f32(self.sweep_rate())
+            .bool(self.is_continuous_sweep_mode_enabled())
+            .bool(self.is_double_buffering_enabled())
+            .u32(self.inter_frame_idle_state() as u32)
+            .u32(self.inter_sweep_idle_state() as u32);
+        for subsweep in self.subsweeps() {
+            fingerprint
+                .i32(subsweep.start_point(self))
+                .u32(subsweep.num_points(self) as u32)
+                .u32(subsweep.step_length(self) as u32)
+                .u32(subsweep.profile(self).to_ffi())
+                .u32(u16::from(subsweep.hwaas(self)) as u32)
+                .u32(subsweep.receiver_gain(self) as u32)
+                .bool(subsweep.is_transmitter_enabled(self))
+                .u32(subsweep.prf(self).into())
+                .bool(subsweep.is_phase_enhancement_enabled(self))
+                .bool(subsweep.is_loopback_enabled(self));
+        }
+        fingerprint.finish()
+    }
+
+    /// Validates the configuration before it is used to prepare the sensor.
+    ///
+    /// This catches some invalid configurations early, instead of failing during `prepare`.
+    /// # Returns
+    /// * `Ok(())` - If no problem was found
+    /// * `Err(ConfigError::TotalPointsExceeded)` - If the total number of points across all
+    ///   subsweeps exceeds [`MAX_TOTAL_POINTS`]
+    /// * `Err(ConfigError::FrameTooLarge)` - If a frame does not fit in the sensor buffer, see
+    ///   [`MAX_FRAME_SAMPLES`]
+    /// * `Err(ConfigError::DoubleBuffering)` - If double buffering is enabled along with
+    ///   continuous sweep mode or with a single sweep per frame
+    /// * `Err(ConfigError::Timing)` - If the configured sweep or frame rate cannot be achieved,
+    ///   see [`min_sweep_duration`](Self::min_sweep_duration)
+    /// * `Err(ConfigError::Subsweep(index))` - If the subsweep at `index` is invalid, e.g. has
+    ///   an out of range HWAAS
+    /// * `Err(ConfigError::StepLength(index))` - If the step length of the subsweep at `index`
+    ///   is neither a divisor nor a multiple of 24
+    /// * `Err(ConfigError::Range(index))` - If the subsweep at `index` ends beyond the maximum
+    ///   unambiguous range of its PRF, given its start point, number of points and step length
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.total_num_points() > MAX_TOTAL_POINTS {
+            return Err(ConfigError::TotalPointsExceeded);
+        }
+        if self.frame_samples() > self.max_frame_samples() {
+            return Err(ConfigError::FrameTooLarge);
+        }
+        if self.is_double_buffering_enabled()
+            && (self.is_continuous_sweep_mode_enabled() || self.sweeps_per_frame() < 2)
+        {
+            return Err(ConfigError::DoubleBuffering);
+        }
+        self.validate_subsweeps()?;
+        self.validate_timing()
+    }
+
+    /// Returns a lower bound of the time needed to measure a sweep, in seconds.
+    ///
+    /// Each averaged sample of each point needs at least one pulse, so a sweep lasts at least
+    /// the sum over all subsweeps of `hwaas * num_points / prf`. The actual duration is longer,
+    /// see [`Radar::check_timing_feasible`](crate::radar::Radar::check_timing_feasible) for a
+    /// check based on the timing computed by the SDK.
+    pub fn min_sweep_duration(&self) -> f32 {
+        self.subsweeps()
+            .map(|subsweep| {
+                let samples =
+                    u16::from(subsweep.hwaas(self)) as f32 * subsweep.num_points(self) as f32;
+                samples / subsweep.prf(self).value() as f32
+            })
+            .sum()
+    }
+
+    /// Checks that the configured sweep and frame rates leave enough time to measure the
+    /// sweeps of all subsweeps, see [`min_sweep_duration`](Self::min_sweep_duration).
+    fn validate_timing(&self) -> Result<(), ConfigError> {
+        let min_sweep_duration = self.min_sweep_duration();
+        let sweep_rate = self.sweep_rate();
+        if sweep_rate > 0.0 && min_sweep_duration * sweep_rate > 1.0 {
+            return Err(ConfigError::Timing);
+        }
+        let frame_rate = self.frame_rate();
+        if frame_rate.is_limited()
+            && min_sweep_duration * self.sweeps_per_frame() as f32 * frame_rate.value() > 1.0
+        {
+            return Err(ConfigError::Timing);
+        }
+        Ok(())
+    }
+
+    /// Returns the number of complex samples in a frame.
+    pub fn frame_samples(&self) -> u32 {
+        self.total_num_points() * self.sweeps_per_frame() as u32
+    }
+
+    /// Returns the maximum number of complex samples in a frame, taking double buffering into
+    /// account.
+    pub fn max_frame_samples(&self) -> u32 {
+        if self.is_double_buffering_enabled() {
+            MAX_FRAME_SAMPLES / 2
+        } else {
+            MAX_FRAME_SAMPLES
+        }
+    }
+
+    /// Checks the constraints that apply to each subsweep individually.
+    ///
+    /// Loopback is not supported with profile 2, and the sensor only supports step lengths that
+    /// are divisors or multiples of 24.
+    /// Subsweeps must end within the maximum unambiguous range of their PRF, so fine step
+    /// lengths with many points may need a lower PRF. A warning is logged for subsweeps ending
+    /// beyond the maximum measurable distance of their PRF but within its maximum unambiguous
+    /// range, where reflections may be ambiguous.
+    fn validate_subsweeps(&self) -> Result<(), ConfigError> {
+        for subsweep in self.subsweeps() {
+            let step_length = subsweep.step_length(self);
+            if step_length == 0 || (24 % step_length != 0 && step_length % 24 != 0) {
+                return Err(ConfigError::StepLength(subsweep.index()));
+            }
+            let end = Points::new(
+                subsweep.start_point(self)
+                    + subsweep.num_points(self).saturating_sub(1) as i32 * step_length as i32,
+            )
+            .to_meters();
+            let prf = subsweep.prf(self);
+            if end > prf.max_unambiguous_range() {
+                return Err(ConfigError::Range(subsweep.index()));
+            }
+            if end > prf.max_measurable_distance() {
+                warn!(
+                    "Subsweep {} ends at {} m, beyond the maximum measurable distance of its PRF",
+                    subsweep.index(),
+                    end
+                );
+            }
+            if !subsweep.hwaas(self).is_valid() {
+                return Err(ConfigError::Subsweep(subsweep.index()));
+            }
+            if subsweep.is_loopback_enabled(self)
+                && subsweep.profile(self) == RadarProfile::AccProfile2
+            {
+                return Err(ConfigError::Subsweep(subsweep.index()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Selects the step length and profile best suited to a target distance resolution.
+    ///
+    /// The step length is the one, among those valid for any profile, whose distance is the
+    /// closest to `target_mm`, with one point being 2.5 mm. The profile is then the lowest one
+    /// for which this step length is valid, see [`RadarProfile::valid_step_lengths`]: lower
+    /// profiles have shorter pulses, so they resolve close reflectors better.
+    ///
+    /// The number of points is adjusted so that the measured range still ends at or beyond
+    /// its previous end, and the resulting configuration is validated.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the configuration was updated and is valid
+    /// * `Err(ConfigError::Resolution)` - If `target_mm` is not a positive number
+    /// * Any error returned by [`validate`](Self::validate)
+    pub fn optimize_for_resolution(&mut self, target_mm: f32) -> Result<(), ConfigError> {
+        if !target_mm.is_finite() || target_mm <= 0.0 {
+            return Err(ConfigError::Resolution);
+        }
+        let point_mm = Points::new(1).to_meters() * 1000.0;
+        let target_points = target_mm / point_mm;
+        let step_length = RadarProfile::AccProfile5
+            .valid_step_lengths()
+            .iter()
+            .copied()
+            .min_by(|a, b| {
+                let (da, db) = (*a as f32 - target_points, *b as f32 - target_points);
+                (da * da).total_cmp(&(db * db))
+            })
+            .unwrap_or(1);
+        let profile = [
+            RadarProfile::AccProfile1,
+            RadarProfile::AccProfile2,
+            RadarProfile::AccProfile3,
+            RadarProfile::AccProfile4,
+            RadarProfile::AccProfile5,
+        ]
+        .into_iter()
+        .find(|profile| profile.is_valid_step_length(step_length))
+        .unwrap_or(RadarProfile::AccProfile5);
+
+        let span = (self.num_points().max(1) as u32 - 1) * self.step_length() as u32;
+        let num_points = span.div_ceil(step_length as u32) + 1;
+        trace!(
+            "Optimizing for {} mm: step length {}, profile {}, {} points",
+            target_mm,
+            step_length,
+            profile.to_ffi(),
+            num_points
+        );
+        self.set_step_length(step_length);
+        self.set_profile(profile);
+        self.set_num_points(u16::try_from(num_points).unwrap_or(u16::MAX));
+        self.validate()
+    }
+
+    /// Chooses the size of the buffer used for sensor calibration.
+    ///
+    /// A larger buffer means fewer transactions between the host and the sensor, so the size
+    /// recommended by the SDK for this configuration, see
+    /// [`config_buffer_size`](Self::config_buffer_size), is chosen if it fits in `cap`.
+    /// Otherwise the buffer is as large as `cap` allows, and the data is moved in several
+    /// transactions.
+    ///
+    /// # Returns
+    /// * `Ok(CalibrationBufferPlan)` - The chosen size, between [`MIN_CALIBRATION_BUFFER_SIZE`]
+    ///   and `cap`, and the number of transactions expected with it
+    /// * `Err(ConfigError::CalibrationBufferTooSmall)` - If `cap` is smaller than
+    ///   [`MIN_CALIBRATION_BUFFER_SIZE`]
+    /// * `Err(ConfigError::BufferSize)` - If the recommended size could not be determined
+    pub fn calibration_buffer_plan(
+        &self,
+        cap: usize,
+    ) -> Result<CalibrationBufferPlan, ConfigError> {
+        if cap < MIN_CALIBRATION_BUFFER_SIZE {
+            return Err(ConfigError::CalibrationBufferTooSmall);
+        }
+        let recommended = (self.config_buffer_size()? as usize).max(MIN_CALIBRATION_BUFFER_SIZE);
+        let size = recommended.min(cap);
+        Ok(CalibrationBufferPlan {
+            size,
+            transactions: recommended.div_ceil(size),
+        })
+    }
+
     /// Get the buffer size needed for the current configuration
     /// # Returns
     /// * `Ok(u32)` - The buffer size needed for the current configuration
@@ -500,3 +1013,46 @@ impl RadarConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrowed_view_does_not_destroy_the_config() {
+        assert!(core::mem::needs_drop::<RadarConfig>());
+        assert!(!core::mem::needs_drop::<RadarConfigRef<'_>>());
+
+        // Dropping a view of a null configuration would crash if it was destroyed
+        {
+            let view = unsafe { RadarConfig::from_raw_borrowed(core::ptr::null()) };
+            assert!(view.ptr().is_null());
+        }
+    }
+
+    #[test]
+    fn rejected_settings_keep_the_generation() {
+        let mut config = RadarConfig::detached();
+
+        assert!(matches!(
+            config.set_hwaas(Hwaas::new(0)),
+            Err(ConfigError::Hwaas)
+        ));
+        assert!(matches!(
+            config.set_num_subsweep(0),
+            Err(ConfigError::NumSubsweep)
+        ));
+        assert!(matches!(
+            config.set_start_distance_m(-1.0),
+            Err(ConfigError::StartPoint)
+        ));
+        assert!(matches!(
+            config.set_all_subsweep_gain(MAX_RECEIVER_GAIN + 1),
+            Err(ConfigError::ReceiverGain)
+        ));
+        assert_eq!(config.generation(), 0);
+
+        config.mark_changed();
+        assert_eq!(config.generation(), 1);
+    }
+}
@@ -0,0 +1,182 @@
+/// A setting of the radar configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum ConfigField {
+    /// Number of subsweeps
+    NumSubsweeps = 0,
+    /// Sweeps per frame
+    SweepsPerFrame,
+    /// Frame rate
+    FrameRate,
+    /// Sweep rate
+    SweepRate,
+    /// Continuous sweep mode
+    ContinuousSweepMode,
+    /// Double buffering
+    DoubleBuffering,
+    /// Inter frame idle state
+    InterFrameIdleState,
+    /// Inter sweep idle state
+    InterSweepIdleState,
+    /// Start point of a subsweep
+    StartPoint,
+    /// Number of points of a subsweep
+    NumPoints,
+    /// Step length of a subsweep
+    StepLength,
+    /// Profile of a subsweep
+    Profile,
+    /// Hardware accelerated average samples of a subsweep
+    Hwaas,
+    /// Receiver gain of a subsweep
+    ReceiverGain,
+    /// Transmitter enable of a subsweep
+    TransmitterEnabled,
+    /// Pulse Repetition Frequency of a subsweep
+    Prf,
+    /// Phase enhancement of a subsweep
+    PhaseEnhancement,
+    /// Loopback of a subsweep
+    Loopback,
+}
+
+impl ConfigField {
+    /// All configuration fields, in declaration order.
+    pub const ALL: [ConfigField; 18] = [
+        ConfigField::NumSubsweeps,
+        ConfigField::SweepsPerFrame,
+        ConfigField::FrameRate,
+        ConfigField::SweepRate,
+        ConfigField::ContinuousSweepMode,
+        ConfigField::DoubleBuffering,
+        ConfigField::InterFrameIdleState,
+        ConfigField::InterSweepIdleState,
+        ConfigField::StartPoint,
+        ConfigField::NumPoints,
+        ConfigField::StepLength,
+        ConfigField::Profile,
+        ConfigField::Hwaas,
+        ConfigField::ReceiverGain,
+        ConfigField::TransmitterEnabled,
+        ConfigField::Prf,
+        ConfigField::PhaseEnhancement,
+        ConfigField::Loopback,
+    ];
+
+    fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+/// Set of configuration fields that differ between two radar configurations.
+///
+/// Subsweep fields are reported if they differ in any of the subsweeps.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigDiff {
+    bits: u32,
+}
+
+impl ConfigDiff {
+    /// Creates an empty diff.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks `field` as different if `differs` is true.
+    pub(super) fn check(&mut self, field: ConfigField, differs: bool) {
+        if differs {
+            self.bits |= field.bit();
+        }
+    }
+
+    /// Returns true if `field` differs.
+    pub fn contains(&self, field: ConfigField) -> bool {
+        self.bits & field.bit() != 0
+    }
+
+    /// Returns true if no field differs.
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns the number of fields that differ.
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Returns the fields that differ.
+    pub fn iter(&self) -> impl Iterator<Item = ConfigField> + '_ {
+        ConfigField::ALL
+            .into_iter()
+            .filter(move |field| self.contains(*field))
+    }
+}
+
+impl core::ops::BitOr for ConfigDiff {
+    type Output = ConfigDiff;
+
+    fn bitor(self, rhs: ConfigDiff) -> Self::Output {
+        ConfigDiff {
+            bits: self.bits | rhs.bits,
+        }
+    }
+}
+
+impl defmt::Format for ConfigDiff {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "[");
+        for (i, field) in self.iter().enumerate() {
+            if i > 0 {
+                defmt::write!(f, ", ");
+            }
+            defmt::write!(f, "{}", field);
+        }
+        defmt::write!(f, "]");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_diff() {
+        let diff = ConfigDiff::new();
+        assert!(diff.is_empty());
+        assert_eq!(diff.len(), 0);
+        assert_eq!(diff.iter().count(), 0);
+    }
+
+    #[test]
+    fn lists_checked_fields_in_declaration_order() {
+        let mut diff = ConfigDiff::new();
+        diff.check(ConfigField::Loopback, true);
+        diff.check(ConfigField::FrameRate, true);
+        diff.check(ConfigField::Prf, false);
+        assert_eq!(diff.len(), 2);
+        assert!(diff.contains(ConfigField::FrameRate));
+        assert!(!diff.contains(ConfigField::Prf));
+        let fields: Vec<ConfigField> = diff.iter().collect();
+        assert_eq!(fields, [ConfigField::FrameRate, ConfigField::Loopback]);
+    }
+
+    #[test]
+    fn union_of_subsweep_diffs() {
+        let mut first = ConfigDiff::new();
+        first.check(ConfigField::Hwaas, true);
+        let mut second = ConfigDiff::new();
+        second.check(ConfigField::Hwaas, true);
+        second.check(ConfigField::StepLength, true);
+        let diff = first | second;
+        assert_eq!(diff.len(), 2);
+        assert!(diff.contains(ConfigField::StepLength));
+    }
+
+    #[test]
+    fn all_fields_have_distinct_bits() {
+        let mut diff = ConfigDiff::new();
+        for field in ConfigField::ALL {
+            diff.check(field, true);
+        }
+        assert_eq!(diff.len(), ConfigField::ALL.len());
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use core::fmt::{Display, Formatter};
+
+#[derive(Debug, defmt::Format)]
 /// Custom errors for radar configuration operations.
 pub enum ConfigError {
     /// Error indicating invalid hardware accelerated average samples setting.
@@ -9,6 +11,130 @@ pub enum ConfigError {
     SweepRate,
     /// Error indicating invalid number of subsweeps setting.
     NumSubsweep,
-
+    /// Error indicating the configuration buffer size could not be determined.
     BufferSize,
+    /// Error indicating the total number of points across all subsweeps exceeds the sensor limit.
+    TotalPointsExceeded,
+    /// Error indicating invalid maximum step length setting.
+    MaxStepLength,
+    /// Error indicating invalid receiver gain setting.
+    ReceiverGain,
+    /// Error indicating an invalid measurement interval.
+    Interval,
+    /// Error indicating an invalid setting in the subsweep at the given index.
+    Subsweep(u8),
+    /// Error indicating double buffering is enabled along with continuous sweep mode or a
+    /// single sweep per frame.
+    DoubleBuffering,
+    /// Error indicating an invalid target distance resolution.
+    Resolution,
+    /// Error indicating an invalid sweep start point.
+    StartPoint,
+    /// Error indicating the configured sweep or frame rate cannot be achieved.
+    Timing,
+    /// Error indicating the step length of the subsweep at the given index is neither a divisor
+    /// nor a multiple of 24.
+    StepLength(u8),
+    /// Error indicating the subsweep at the given index ends beyond the maximum unambiguous
+    /// range of its PRF.
+    Range(u8),
+    /// Error indicating a frame does not fit in the sensor buffer.
+    FrameTooLarge,
+    /// Error indicating a calibration buffer cap smaller than the minimum calibration buffer size.
+    CalibrationBufferTooSmall,
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let message = match self {
+            ConfigError::Hwaas => "invalid hardware accelerated average samples",
+            ConfigError::ContinuousSweepMode => "invalid continuous sweep mode",
+            ConfigError::SweepRate => "invalid sweep rate",
+            ConfigError::NumSubsweep => "invalid number of subsweeps",
+            ConfigError::BufferSize => "could not determine the configuration buffer size",
+            ConfigError::TotalPointsExceeded => "total number of points exceeds the sensor limit",
+            ConfigError::MaxStepLength => "invalid maximum step length",
+            ConfigError::ReceiverGain => "invalid receiver gain",
+            ConfigError::Interval => "invalid measurement interval",
+            ConfigError::DoubleBuffering => {
+                "double buffering requires continuous sweep mode disabled and several sweeps per frame"
+            }
+            ConfigError::Resolution => "invalid target resolution",
+            ConfigError::StartPoint => "invalid start point",
+            ConfigError::Timing => "sweep or frame rate cannot be achieved",
+            ConfigError::FrameTooLarge => "frame does not fit in the sensor buffer",
+            ConfigError::CalibrationBufferTooSmall => {
+                "calibration buffer is smaller than the minimum size"
+            }
+            ConfigError::Subsweep(index) => {
+                return write!(f, "invalid setting in subsweep {}", index);
+            }
+            ConfigError::StepLength(index) => {
+                return write!(
+                    f,
+                    "step length of subsweep {} is not a divisor or multiple of 24",
+                    index
+                );
+            }
+            ConfigError::Range(index) => {
+                return write!(
+                    f,
+                    "subsweep {} ends beyond the maximum unambiguous range of its PRF",
+                    index
+                );
+            }
+        };
+        f.write_str(message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    fn each_variant_has_a_distinct_message() {
+        let errors = [
+            ConfigError::Hwaas,
+            ConfigError::ContinuousSweepMode,
+            ConfigError::SweepRate,
+            ConfigError::NumSubsweep,
+            ConfigError::BufferSize,
+            ConfigError::TotalPointsExceeded,
+            ConfigError::MaxStepLength,
+            ConfigError::ReceiverGain,
+            ConfigError::Interval,
+            ConfigError::Subsweep(1),
+            ConfigError::DoubleBuffering,
+            ConfigError::Resolution,
+            ConfigError::StartPoint,
+            ConfigError::Timing,
+            ConfigError::StepLength(1),
+            ConfigError::Range(1),
+            ConfigError::FrameTooLarge,
+            ConfigError::CalibrationBufferTooSmall,
+        ];
+        let mut messages: Vec<_> = errors.iter().map(ToString::to_string).collect();
+        assert!(messages.iter().all(|message| !message.is_empty()));
+        messages.sort();
+        messages.dedup();
+        assert_eq!(messages.len(), errors.len());
+    }
+
+    #[test]
+    fn subsweep_index_is_rendered() {
+        assert_eq!(
+            ConfigError::Subsweep(2).to_string(),
+            "invalid setting in subsweep 2"
+        );
+        assert_ne!(
+            ConfigError::Range(0).to_string(),
+            ConfigError::Range(1).to_string()
+        );
+    }
 }
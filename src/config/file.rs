@@ -0,0 +1,187 @@
+use std::fmt::{Display, Formatter};
+
+use serde::Deserialize;
+
+use crate::config::error::ConfigError;
+use crate::config::frame_rate::FrameRate;
+use crate::config::hwaas::Hwaas;
+use crate::config::prf::PulseRepetitionFrequency;
+use crate::config::profile::RadarProfile;
+use crate::config::{RadarConfig, RadarIdleState};
+
+/// Error returned when loading a radar configuration from a file.
+#[derive(Debug)]
+pub enum ConfigFileError {
+    /// The TOML document could not be parsed
+    Toml(toml::de::Error),
+    /// The JSON document could not be parsed
+    Json(serde_json::Error),
+    /// The value of the given key is not valid
+    InvalidValue(&'static str),
+    /// The resulting configuration is invalid
+    Invalid(ConfigError),
+}
+
+impl Display for ConfigFileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigFileError::Toml(error) => write!(f, "invalid TOML: {}", error),
+            ConfigFileError::Json(error) => write!(f, "invalid JSON: {}", error),
+            ConfigFileError::InvalidValue(key) => write!(f, "invalid value for `{}`", key),
+            ConfigFileError::Invalid(error) => write!(f, "invalid configuration: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ConfigFileError {}
+
+impl From<ConfigError> for ConfigFileError {
+    fn from(error: ConfigError) -> Self {
+        ConfigFileError::Invalid(error)
+    }
+}
+
+/// Settings read from a configuration file, every key being optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    start_point: Option<i32>,
+    start_distance_m: Option<f32>,
+    num_points: Option<u16>,
+    step_length: Option<u16>,
+    profile: Option<u32>,
+    prf: Option<String>,
+    hwaas: Option<u16>,
+    receiver_gain: Option<u8>,
+    sweeps_per_frame: Option<u16>,
+    frame_rate: Option<f32>,
+    phase_enhancement: Option<bool>,
+    loopback: Option<bool>,
+    double_buffering: Option<bool>,
+    transmitter_enabled: Option<bool>,
+    inter_frame_idle_state: Option<String>,
+    inter_sweep_idle_state: Option<String>,
+}
+
+impl RadarConfig {
+    /// Loads a configuration from a TOML document, see [`from_json`](Self::from_json) for the
+    /// supported keys.
+    ///
+    /// ```toml
+    /// start_distance_m = 0.5
+    /// num_points = 100
+    /// step_length = 4
+    /// profile = 3
+    /// prf = "13.0 MHz"
+    /// hwaas = 16
+    /// ```
+    pub fn from_toml(document: &str) -> Result<Self, ConfigFileError> {
+        let file: ConfigFile = toml::from_str(document).map_err(ConfigFileError::Toml)?;
+        Self::from_file(file)
+    }
+
+    /// Loads a configuration from a JSON document.
+    ///
+    /// Every key is optional, settings that are not given keep their default value:
+    /// * `start_point` or `start_distance_m`, the start of the sweep in points or meters
+    /// * `num_points`, `step_length`, `hwaas`, `receiver_gain` and `sweeps_per_frame`
+    /// * `profile`, from 1 to 5
+    /// * `prf`, as labelled by [`PulseRepetitionFrequency::label`], e.g. `"15.6 MHz"`
+    /// * `frame_rate` in Hz, 0 meaning unlimited
+    /// * `phase_enhancement`, `loopback`, `double_buffering` and `transmitter_enabled`
+    /// * `inter_frame_idle_state` and `inter_sweep_idle_state`, one of `"deep_sleep"`,
+    ///   `"sleep"` and `"ready"`
+    ///
+    /// Unknown keys are rejected and the configuration is validated, see
+    /// [`validate`](Self::validate).
+    pub fn from_json(document: &str) -> Result<Self, ConfigFileError> {
+        let file: ConfigFile = serde_json::from_str(document).map_err(ConfigFileError::Json)?;
+        Self::from_file(file)
+    }
+
+    fn from_file(file: ConfigFile) -> Result<Self, ConfigFileError> {
+        let mut config = RadarConfig::new();
+        if let Some(start_point) = file.start_point {
+            config.set_start_point(start_point);
+        }
+        if let Some(meters) = file.start_distance_m {
+            config
+                .set_start_distance_m(meters)
+                .map_err(|_| ConfigFileError::InvalidValue("start_distance_m"))?;
+        }
+        if let Some(num_points) = file.num_points {
+            config.set_num_points(num_points);
+        }
+        if let Some(step_length) = file.step_length {
+            config.set_step_length(step_length);
+        }
+        // The profile is set before the PRF, which it may change
+        if let Some(profile) = file.profile {
+            if !(1..=5).contains(&profile) {
+                return Err(ConfigFileError::InvalidValue("profile"));
+            }
+            config.set_profile(RadarProfile::from(profile));
+        }
+        if let Some(prf) = file.prf {
+            let prf = PulseRepetitionFrequency::parse(&prf)
+                .ok_or(ConfigFileError::InvalidValue("prf"))?;
+            config.set_prf(prf);
+        }
+        if let Some(hwaas) = file.hwaas {
+            config
+                .set_hwaas(Hwaas::new(hwaas))
+                .map_err(|_| ConfigFileError::InvalidValue("hwaas"))?;
+        }
+        if let Some(receiver_gain) = file.receiver_gain {
+            config.receiver_gain_set(receiver_gain);
+        }
+        if let Some(sweeps_per_frame) = file.sweeps_per_frame {
+            config.set_sweeps_per_frame(sweeps_per_frame);
+        }
+        if let Some(frame_rate) = file.frame_rate {
+            if !frame_rate.is_finite() || frame_rate < 0.0 {
+                return Err(ConfigFileError::InvalidValue("frame_rate"));
+            }
+            config.set_frame_rate(if frame_rate == 0.0 {
+                FrameRate::Unlimited
+            } else {
+                FrameRate::Limited(frame_rate)
+            });
+        }
+        if let Some(enable) = file.phase_enhancement {
+            config.set_phase_enhancement(enable);
+        }
+        if let Some(enable) = file.loopback {
+            config.set_loopback(enable);
+        }
+        if let Some(enable) = file.double_buffering {
+            config.set_double_buffering(enable);
+        }
+        if let Some(enable) = file.transmitter_enabled {
+            config.set_transmitter_enabled(enable);
+        }
+        if let Some(state) = file.inter_frame_idle_state {
+            config.set_inter_frame_idle_state(
+                parse_idle_state(&state)
+                    .ok_or(ConfigFileError::InvalidValue("inter_frame_idle_state"))?,
+            );
+        }
+        if let Some(state) = file.inter_sweep_idle_state {
+            config.set_inter_sweep_idle_state(
+                parse_idle_state(&state)
+                    .ok_or(ConfigFileError::InvalidValue("inter_sweep_idle_state"))?,
+            );
+        }
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+fn parse_idle_state(state: &str) -> Option<RadarIdleState> {
+    match state {
+        "deep_sleep" => Some(RadarIdleState::DeepSleep),
+        "sleep" => Some(RadarIdleState::Sleep),
+        "ready" => Some(RadarIdleState::Ready),
+        _ => None,
+    }
+}
@@ -0,0 +1,78 @@
+/// Stable 64-bit FNV-1a hash of configuration settings.
+///
+/// Values are hashed in little endian, so a fingerprint does not depend on the platform nor on
+/// the address of the configuration.
+pub(crate) struct Fingerprint {
+    hash: u64,
+}
+
+impl Fingerprint {
+    pub(crate) fn new() -> Self {
+        Self {
+            hash: 0xcbf2_9ce4_8422_2325,
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.hash = (self.hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    pub(crate) fn u32(&mut self, value: u32) -> &mut Self {
+        self.write(&value.to_le_bytes());
+        self
+    }
+
+    pub(crate) fn i32(&mut self, value: i32) -> &mut Self {
+        self.write(&value.to_le_bytes());
+        self
+    }
+
+    pub(crate) fn f32(&mut self, value: f32) -> &mut Self {
+        self.u32(value.to_bits())
+    }
+
+    pub(crate) fn bool(&mut self, value: bool) -> &mut Self {
+        self.u32(value as u32)
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_fingerprint_is_the_fnv_offset_basis() {
+        assert_eq!(Fingerprint::new().finish(), 0xcbf2_9ce4_8422_2325);
+    }
+
+    #[test]
+    fn fingerprint_is_stable() {
+        let mut fingerprint = Fingerprint::new();
+        fingerprint.u32(1).i32(-2).bool(true);
+        assert_eq!(fingerprint.finish(), 0xbad2_052a_9881_a690);
+    }
+
+    #[test]
+    fn fingerprint_depends_on_order() {
+        let mut a = Fingerprint::new();
+        a.u32(1).u32(2);
+        let mut b = Fingerprint::new();
+        b.u32(2).u32(1);
+        assert_ne!(a.finish(), b.finish());
+    }
+
+    #[test]
+    fn f32_fingerprint_uses_the_bit_pattern() {
+        let mut a = Fingerprint::new();
+        a.f32(1.5);
+        let mut b = Fingerprint::new();
+        b.u32(1.5f32.to_bits());
+        assert_eq!(a.finish(), b.finish());
+    }
+}
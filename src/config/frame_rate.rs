@@ -1,4 +1,4 @@
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 /// Frame rate options for the radar configuration.
 pub enum FrameRate {
     /// No limit on the frame rate.
@@ -5,9 +5,20 @@ use crate::config::error::ConfigError;
 pub struct Hwaas(u16);
 
 impl Hwaas {
+    /// Minimum number of hardware accelerated average samples supported by the sensor.
+    pub const MIN: u16 = 1;
+    /// Maximum number of hardware accelerated average samples supported by the sensor.
+    pub const MAX: u16 = 511;
+
     pub fn new(value: u16) -> Self {
         Self(value)
     }
+
+    /// Returns true if the value is between [`MIN`](Self::MIN) and [`MAX`](Self::MAX)
+    /// inclusive.
+    pub fn is_valid(&self) -> bool {
+        (Self::MIN..=Self::MAX).contains(&self.0)
+    }
 }
 
 impl TryFrom<u16> for Hwaas {
@@ -1,4 +1,6 @@
+use crate::config::profile::RadarProfile;
 use a121_sys::acc_config_prf_t;
+use core::fmt::{Display, Formatter};
 
 /// Pulse Repetition Frequency (PRF)
 ///
@@ -22,7 +24,9 @@ use a121_sys::acc_config_prf_t;
 /// | Prf5_2Mhz           | 5.2 MHz  | 24.3m| 28.8m |
 ///
 /// *19.5MHz is only available for profile 1.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Frequencies are ordered from the highest to the lowest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum PulseRepetitionFrequency {
     /// 19.5 MHz (Available only for profile 1)
     Prf19_5Mhz = 0,
@@ -60,7 +64,54 @@ impl From<PulseRepetitionFrequency> for u32 {
     }
 }
 
+impl From<PulseRepetitionFrequency> for &'static str {
+    fn from(prf: PulseRepetitionFrequency) -> Self {
+        prf.label()
+    }
+}
+
+impl Display for PulseRepetitionFrequency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
 impl PulseRepetitionFrequency {
+    const ALL: [PulseRepetitionFrequency; 6] = [
+        PulseRepetitionFrequency::Prf19_5Mhz,
+        PulseRepetitionFrequency::Prf15_6Mhz,
+        PulseRepetitionFrequency::Prf13_0Mhz,
+        PulseRepetitionFrequency::Prf8_7Mhz,
+        PulseRepetitionFrequency::Prf6_5Mhz,
+        PulseRepetitionFrequency::Prf5_2Mhz,
+    ];
+
+    /// Returns true if the PRF can be used with `profile`, the 19.5 MHz PRF being only
+    /// available for profile 1.
+    pub fn is_supported_by(&self, profile: RadarProfile) -> bool {
+        *self != PulseRepetitionFrequency::Prf19_5Mhz || profile == RadarProfile::AccProfile1
+    }
+
+    /// Returns a human-readable label of the PRF, e.g. "15.6 MHz".
+    pub fn label(&self) -> &'static str {
+        match self {
+            PulseRepetitionFrequency::Prf19_5Mhz => "19.5 MHz",
+            PulseRepetitionFrequency::Prf15_6Mhz => "15.6 MHz",
+            PulseRepetitionFrequency::Prf13_0Mhz => "13.0 MHz",
+            PulseRepetitionFrequency::Prf8_7Mhz => "8.7 MHz",
+            PulseRepetitionFrequency::Prf6_5Mhz => "6.5 MHz",
+            PulseRepetitionFrequency::Prf5_2Mhz => "5.2 MHz",
+        }
+    }
+
+    /// Parses a label as returned by [`label`](Self::label), ignoring surrounding whitespace.
+    ///
+    /// Returns `None` if the label does not match any PRF.
+    pub fn parse(label: &str) -> Option<Self> {
+        let label = label.trim();
+        Self::ALL.into_iter().find(|prf| prf.label() == label)
+    }
+
     /// Returns the PRF value in Hz.
     pub fn value(&self) -> u32 {
         match self {
@@ -84,4 +135,43 @@ impl PulseRepetitionFrequency {
             PulseRepetitionFrequency::Prf5_2Mhz => 24.3,
         }
     }
+
+    /// Returns the maximum unambiguous range in meters.
+    pub fn max_unambiguous_range(&self) -> f32 {
+        match self {
+            PulseRepetitionFrequency::Prf19_5Mhz => 7.7,
+            PulseRepetitionFrequency::Prf15_6Mhz => 9.6,
+            PulseRepetitionFrequency::Prf13_0Mhz => 11.5,
+            PulseRepetitionFrequency::Prf8_7Mhz => 17.3,
+            PulseRepetitionFrequency::Prf6_5Mhz => 23.1,
+            PulseRepetitionFrequency::Prf5_2Mhz => 28.8,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn labels_round_trip_for_every_variant() {
+        let mut count = 0;
+        for prf in (0..).map_while(|raw| PulseRepetitionFrequency::try_from(raw).ok()) {
+            assert_eq!(PulseRepetitionFrequency::parse(prf.label()), Some(prf));
+            assert_eq!(PulseRepetitionFrequency::parse(&prf.to_string()), Some(prf));
+            count += 1;
+        }
+        assert_eq!(count, PulseRepetitionFrequency::ALL.len());
+    }
+
+    #[test]
+    fn parse_ignores_surrounding_whitespace_only() {
+        assert_eq!(
+            PulseRepetitionFrequency::parse(" 8.7 MHz\n"),
+            Some(PulseRepetitionFrequency::Prf8_7Mhz)
+        );
+        assert_eq!(PulseRepetitionFrequency::parse("8.7MHz"), None);
+        assert_eq!(PulseRepetitionFrequency::parse(""), None);
+    }
 }
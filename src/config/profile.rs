@@ -1,6 +1,8 @@
+use core::fmt::{Display, Formatter};
+
 use a121_sys::acc_config_profile_t_ACC_CONFIG_PROFILE_1;
 
-#[derive(Debug, PartialEq, defmt::Format)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, defmt::Format)]
 /// Radar profiles indicating different settings for the sensor's RX and TX paths.
 pub enum RadarProfile {
     /// Profile 1
@@ -27,3 +29,50 @@ impl From<u32> for RadarProfile {
         }
     }
 }
+
+impl RadarProfile {
+    /// Creates a radar profile from its SDK representation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value does not correspond to a radar profile.
+    pub fn from_ffi(value: u32) -> Self {
+        value.into()
+    }
+
+    /// Returns the SDK representation of the radar profile.
+    pub fn to_ffi(&self) -> u32 {
+        match self {
+            RadarProfile::AccProfile1 => 1,
+            RadarProfile::AccProfile2 => 2,
+            RadarProfile::AccProfile3 => 3,
+            RadarProfile::AccProfile4 => 4,
+            RadarProfile::AccProfile5 => 5,
+        }
+    }
+
+    /// Returns the step lengths, in points, recommended for this profile, in increasing order.
+    ///
+    /// The sensor only supports step lengths that are divisors or multiples of 24. Of these,
+    /// the listed ones do not exceed half the envelope width of the profile, so that no
+    /// reflector is missed between two points.
+    pub fn valid_step_lengths(&self) -> &'static [u16] {
+        match self {
+            RadarProfile::AccProfile1 => &[1, 2, 3, 4, 6, 8],
+            RadarProfile::AccProfile2 => &[1, 2, 3, 4, 6, 8, 12],
+            RadarProfile::AccProfile3 | RadarProfile::AccProfile4 => &[1, 2, 3, 4, 6, 8, 12, 24],
+            RadarProfile::AccProfile5 => &[1, 2, 3, 4, 6, 8, 12, 24, 48],
+        }
+    }
+
+    /// Returns true if `step_length` is one of [`valid_step_lengths`](Self::valid_step_lengths).
+    pub fn is_valid_step_length(&self, step_length: u16) -> bool {
+        self.valid_step_lengths().contains(&step_length)
+    }
+}
+
+impl Display for RadarProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Profile {}", self.to_ffi())
+    }
+}
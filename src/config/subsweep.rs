@@ -1,3 +1,4 @@
+use crate::config::diff::{ConfigDiff, ConfigField};
 use crate::config::hwaas::Hwaas;
 use crate::config::prf::PulseRepetitionFrequency;
 use crate::config::profile::RadarProfile;
@@ -15,12 +16,17 @@ impl Subsweep {
         Self { index }
     }
 
+    /// Returns the index of the subsweep
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
     /// Sets start point for subsweep
     /// # Arguments
     /// * `config` - A reference to a `Config` instance.
     /// * `start_point` - The start point for the subsweep.
     pub fn set_start_point(&self, config: &mut RadarConfig, start_point: i32) {
-        unsafe { acc_config_subsweep_start_point_set(config.inner, start_point, self.index) };
+        unsafe { acc_config_subsweep_start_point_set(config.inner_mut(), start_point, self.index) };
     }
 
     /// Gets start point for subsweep
@@ -33,7 +39,7 @@ impl Subsweep {
     /// * `config` - A reference to a `Config` instance.
     /// * `num_points` - The number of points for the subsweep.
     pub fn set_num_points(&self, config: &mut RadarConfig, num_points: u16) {
-        unsafe { acc_config_subsweep_num_points_set(config.inner, num_points, self.index) };
+        unsafe { acc_config_subsweep_num_points_set(config.inner_mut(), num_points, self.index) };
     }
 
     /// Gets number of points for subsweep
@@ -46,7 +52,7 @@ impl Subsweep {
     /// * `config` - A reference to a `Config` instance.
     /// * `step_length` - The step length for the subsweep.
     pub fn set_step_length(&self, config: &mut RadarConfig, step_length: u16) {
-        unsafe { acc_config_subsweep_step_length_set(config.inner, step_length, self.index) };
+        unsafe { acc_config_subsweep_step_length_set(config.inner_mut(), step_length, self.index) };
     }
 
     /// Gets step length for subsweep
@@ -58,8 +64,20 @@ impl Subsweep {
     /// # Arguments
     /// * `config` - A reference to a `Config` instance.
     /// * `profile` - The profile for the subsweep.
+    ///
+    /// Like [`RadarConfig::set_profile`], lowers the PRF of the subsweep to 15.6 MHz if it is
+    /// 19.5 MHz and `profile` is not profile 1.
     pub fn set_profile(&self, config: &mut RadarConfig, profile: RadarProfile) {
-        unsafe { acc_config_subsweep_profile_set(config.inner, profile as u32, self.index) };
+        unsafe {
+            acc_config_subsweep_profile_set(config.inner_mut(), profile.to_ffi(), self.index)
+        };
+        if !self.prf(config).is_supported_by(profile) {
+            defmt::warn!(
+                "19.5 MHz PRF requires profile 1, using 15.6 MHz for subsweep {}",
+                self.index
+            );
+            self.set_prf(config, PulseRepetitionFrequency::Prf15_6Mhz);
+        }
     }
 
     /// Gets profile for subsweep
@@ -72,7 +90,7 @@ impl Subsweep {
     /// * `config` - A reference to a `Config` instance.
     /// * `hwaas` - The Hardware accelerated average samples for the subsweep.
     pub fn set_hwaas(&self, config: &mut RadarConfig, hwaas: Hwaas) {
-        unsafe { acc_config_subsweep_hwaas_set(config.inner, hwaas.into(), self.index) };
+        unsafe { acc_config_subsweep_hwaas_set(config.inner_mut(), hwaas.into(), self.index) };
     }
 
     /// Gets Hardware accelerated average samples for subsweep
@@ -85,7 +103,7 @@ impl Subsweep {
     /// * `config` - A reference to a `Config` instance.
     /// * `gain` - The receiver gain for the subsweep.
     pub fn set_receiver_gain(&self, config: &mut RadarConfig, gain: u8) {
-        unsafe { acc_config_subsweep_receiver_gain_set(config.inner, gain, self.index) };
+        unsafe { acc_config_subsweep_receiver_gain_set(config.inner_mut(), gain, self.index) };
     }
 
     /// Gets receiver gain for subsweep
@@ -98,7 +116,7 @@ impl Subsweep {
     /// * `config` - A reference to a `Config` instance.
     /// * `enable` - The transmitter enabled for the subsweep.
     pub fn set_transmitter_enabled(&self, config: &mut RadarConfig, enable: bool) {
-        unsafe { acc_config_subsweep_enable_tx_set(config.inner, enable, self.index) };
+        unsafe { acc_config_subsweep_enable_tx_set(config.inner_mut(), enable, self.index) };
     }
 
     /// Gets transmitter enabled for subsweep
@@ -111,7 +129,7 @@ impl Subsweep {
     /// * `config` - A reference to a `Config` instance.
     /// µ 'prf' - Pulse Repetition Frequency for the subsweep.
     pub fn set_prf(&self, config: &mut RadarConfig, prf: PulseRepetitionFrequency) {
-        unsafe { acc_config_subsweep_prf_set(config.inner, prf.into(), self.index) };
+        unsafe { acc_config_subsweep_prf_set(config.inner_mut(), prf.into(), self.index) };
     }
 
     /// Gets Pulse Repetition Frequency for subsweep
@@ -122,7 +140,9 @@ impl Subsweep {
 
     /// Set the phase enhancement enabled configuration
     pub fn set_phase_enhancement_enabled(&self, config: &mut RadarConfig, enable: bool) {
-        unsafe { acc_config_subsweep_phase_enhancement_set(config.inner, enable, self.index) };
+        unsafe {
+            acc_config_subsweep_phase_enhancement_set(config.inner_mut(), enable, self.index)
+        };
     }
 
     /// Get the phase enhancement enabled configuration
@@ -132,11 +152,132 @@ impl Subsweep {
 
     /// Set the loopback enabled configuration
     pub fn set_loopback_enabled(&self, config: &mut RadarConfig, enable: bool) {
-        unsafe { acc_config_subsweep_enable_loopback_set(config.inner, enable, self.index) };
+        unsafe { acc_config_subsweep_enable_loopback_set(config.inner_mut(), enable, self.index) };
     }
 
     /// Get the loopback enabled configuration
     pub fn is_loopback_enabled(&self, config: &RadarConfig) -> bool {
         unsafe { acc_config_subsweep_enable_loopback_get(config.inner, self.index) }
     }
+
+    /// Returns the settings of this subsweep that differ between both configurations.
+    pub(super) fn diff(&self, config: &RadarConfig, other: &RadarConfig) -> ConfigDiff {
+        let mut diff = ConfigDiff::new();
+        diff.check(
+            ConfigField::StartPoint,
+            self.start_point(config) != self.start_point(other),
+        );
+        diff.check(
+            ConfigField::NumPoints,
+            self.num_points(config) != self.num_points(other),
+        );
+        diff.check(
+            ConfigField::StepLength,
+            self.step_length(config) != self.step_length(other),
+        );
+        diff.check(
+            ConfigField::Profile,
+            self.profile(config) != self.profile(other),
+        );
+        diff.check(ConfigField::Hwaas, self.hwaas(config) != self.hwaas(other));
+        diff.check(
+            ConfigField::ReceiverGain,
+            self.receiver_gain(config) != self.receiver_gain(other),
+        );
+        diff.check(
+            ConfigField::TransmitterEnabled,
+            self.is_transmitter_enabled(config) != self.is_transmitter_enabled(other),
+        );
+        diff.check(ConfigField::Prf, self.prf(config) != self.prf(other));
+        diff.check(
+            ConfigField::PhaseEnhancement,
+            self.is_phase_enhancement_enabled(config) != self.is_phase_enhancement_enabled(other),
+        );
+        diff.check(
+            ConfigField::Loopback,
+            self.is_loopback_enabled(config) != self.is_loopback_enabled(other),
+        );
+        diff
+    }
+}
+
+/// Chainable configurator for a single subsweep.
+///
+/// Obtained through [`RadarConfig::subsweep_mut`], it borrows the configuration so several
+/// settings can be applied to one subsweep in a single expression.
+pub struct SubsweepConfigurator<'a> {
+    config: &'a mut RadarConfig,
+    subsweep: Subsweep,
+}
+
+impl<'a> SubsweepConfigurator<'a> {
+    pub(super) fn new(config: &'a mut RadarConfig, subsweep: Subsweep) -> Self {
+        Self { config, subsweep }
+    }
+
+    /// Returns the subsweep being configured.
+    pub fn subsweep(&self) -> Subsweep {
+        self.subsweep
+    }
+
+    /// Sets start point for subsweep
+    pub fn start_point(&mut self, start_point: i32) -> &mut Self {
+        self.subsweep.set_start_point(self.config, start_point);
+        self
+    }
+
+    /// Sets number of points for subsweep
+    pub fn num_points(&mut self, num_points: u16) -> &mut Self {
+        self.subsweep.set_num_points(self.config, num_points);
+        self
+    }
+
+    /// Sets step length for subsweep
+    pub fn step_length(&mut self, step_length: u16) -> &mut Self {
+        self.subsweep.set_step_length(self.config, step_length);
+        self
+    }
+
+    /// Sets profile for subsweep
+    pub fn profile(&mut self, profile: RadarProfile) -> &mut Self {
+        self.subsweep.set_profile(self.config, profile);
+        self
+    }
+
+    /// Sets Hardware accelerated average samples for subsweep
+    pub fn hwaas(&mut self, hwaas: Hwaas) -> &mut Self {
+        self.subsweep.set_hwaas(self.config, hwaas);
+        self
+    }
+
+    /// Sets receiver gain for subsweep
+    pub fn receiver_gain(&mut self, gain: u8) -> &mut Self {
+        self.subsweep.set_receiver_gain(self.config, gain);
+        self
+    }
+
+    /// Sets transmitter enabled for subsweep
+    pub fn transmitter_enabled(&mut self, enable: bool) -> &mut Self {
+        self.subsweep.set_transmitter_enabled(self.config, enable);
+        self
+    }
+
+    /// Sets Pulse Repetition Frequency for subsweep
+    pub fn prf(&mut self, prf: PulseRepetitionFrequency) -> &mut Self {
+        self.subsweep.set_prf(self.config, prf);
+        self
+    }
+
+    /// Sets the phase enhancement enabled configuration
+    pub fn phase_enhancement_enabled(&mut self, enable: bool) -> &mut Self {
+        self.subsweep
+            .set_phase_enhancement_enabled(self.config, enable);
+        self
+    }
+
+    /// Sets the loopback enabled configuration
+    pub fn loopback_enabled(&mut self, enable: bool) -> &mut Self {
+        self.subsweep.set_loopback_enabled(self.config, enable);
+        self
+    }
 }
@@ -4,3 +4,87 @@ pub mod distance;
 #[cfg(feature = "presence")]
 /// Presence detector module
 pub mod presence;
+
+use crate::sensor::error::SensorError;
+
+/// Common measurement cycle of the detectors, letting generic code such as logging harnesses
+/// or test rigs drive any detector.
+///
+/// The detector must have been calibrated and prepared beforehand, as required by each
+/// detector.
+#[allow(async_fn_in_trait)]
+pub trait Detector {
+    /// Buffers needed to measure and process the data
+    type Buffers: ?Sized;
+    /// Result of processing one measurement
+    type Result<'a>
+    where
+        Self: 'a;
+
+    /// Performs one measurement and processes its data.
+    async fn measure_once(
+        &mut self,
+        buffers: &mut Self::Buffers,
+    ) -> Result<Self::Result<'_>, SensorError>;
+}
+
+/// Holds the state of a detector owning its radar while the detector is not lent out.
+#[cfg(any(feature = "distance", feature = "presence"))]
+pub(crate) struct DetachedSlot<T>(Option<T>);
+
+#[cfg(any(feature = "distance", feature = "presence"))]
+impl<T> DetachedSlot<T> {
+    pub(crate) fn new(detached: T) -> Self {
+        Self(Some(detached))
+    }
+
+    /// Takes the state out to lend the detector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the state was not put back, i.e. a previously lent detector was leaked.
+    pub(crate) fn take(&mut self) -> T {
+        self.0
+            .take()
+            .expect("detector state lost by a leaked detector reference")
+    }
+
+    /// Puts the state back once the lent detector is dropped.
+    pub(crate) fn put(&mut self, detached: T) {
+        self.0 = Some(detached);
+    }
+}
+
+/// Warns if a detector is configured for another sensor than the radar it uses, before the
+/// configuration is synced to the radar's sensor.
+pub(crate) fn warn_sensor_mismatch(radar_id: u32, config_id: u32) {
+    if radar_id != config_id {
+        defmt::warn!(
+            "Detector configured for sensor {} but the radar uses sensor {}, using the radar's",
+            config_id,
+            radar_id
+        );
+    }
+}
+
+#[cfg(all(test, any(feature = "distance", feature = "presence")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lent_state_is_returned() {
+        let mut slot = DetachedSlot::new(1);
+        let mut lent = slot.take();
+        lent += 1;
+        slot.put(lent);
+        assert_eq!(slot.take(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "detector state lost")]
+    fn leaked_state_is_detected() {
+        let mut slot = DetachedSlot::new(1);
+        core::mem::forget(slot.take());
+        slot.take();
+    }
+}
@@ -1,13 +1,32 @@
+#[cfg(not(feature = "no-alloc"))]
+pub mod buffers;
 pub mod config;
+pub mod owned;
 pub mod results;
+pub mod stats;
+pub mod threshold;
 
+#[cfg(not(feature = "no-alloc"))]
+use alloc::vec;
+#[cfg(not(feature = "no-alloc"))]
+use alloc::vec::Vec;
+
+use crate::config::{RadarConfig, RadarConfigRef};
+#[cfg(not(feature = "no-alloc"))]
+use crate::detector::distance::buffers::DistanceBuffers;
 use crate::detector::distance::config::RadarDistanceConfig;
-use crate::detector::distance::results::{DistanceSizes, ProcessDataError};
+use crate::detector::distance::results::{
+    DistanceMemoryCalculator, DistanceSizes, MemoryReport, ProcessDataError,
+};
+use crate::detector::warn_sensor_mismatch;
+#[cfg(not(feature = "no-alloc"))]
+use crate::detector::Detector;
 use crate::radar::{Radar, Ready};
 use crate::sensor::calibration::CalibrationResult;
 use crate::sensor::error::SensorError;
 use a121_sys::*;
 use core::ffi::c_void;
+use core::time::Duration;
 use defmt::trace;
 use embedded_hal::digital::OutputPin;
 use embedded_hal_async::delay::DelayNs;
@@ -40,6 +59,33 @@ impl Drop for InnerRadarDistanceDetector {
     }
 }
 
+/// Working memory used to calibrate the distance detector
+enum CalibrationBuffer<'b> {
+    /// A buffer provided by the caller
+    Caller(&'b mut [u8]),
+    /// The scratch buffer of the radar
+    Scratch,
+}
+
+impl CalibrationBuffer<'_> {
+    /// Returns the working memory, borrowing the radar scratch buffer only as long as needed
+    /// so that it is never taken out of the radar.
+    fn get<'s, SINT, ENABLE, DLY>(
+        &'s mut self,
+        radar: &'s mut Radar<Ready, SINT, ENABLE, DLY>,
+    ) -> &'s mut [u8]
+    where
+        SINT: Wait,
+        ENABLE: OutputPin,
+        DLY: DelayNs,
+    {
+        match self {
+            CalibrationBuffer::Caller(buffer) => buffer,
+            CalibrationBuffer::Scratch => &mut radar.scratch,
+        }
+    }
+}
+
 /// The main structure representing the radar distance detector.
 pub struct RadarDistanceDetector<'radar, SINT, ENABLE, DLY>
 where
@@ -52,6 +98,55 @@ where
     inner: InnerRadarDistanceDetector,
     /// Configuration for the radar distance detection.
     pub config: RadarDistanceConfig,
+    timeout: Option<Duration>,
+    close_range_calibrated: bool,
+    strict: bool,
+    yield_during_calibration: bool,
+    /// Generation of the radar configuration the detector was created or reset with
+    radar_config_generation: u32,
+    /// Sensor configuration used by the detector for the last processed result
+    effective_sensor_config: *const acc_config_t,
+    /// Tag of the buffer filled by the last measurement, checked when processing it
+    #[cfg(debug_assertions)]
+    measured_buffer: Option<BufferTag>,
+}
+
+/// State of a distance detector apart from its radar, kept by
+/// [`OwnedDistanceDetector`](owned::OwnedDistanceDetector) between two borrows of the radar.
+struct DetachedDistanceDetector {
+    inner: InnerRadarDistanceDetector,
+    config: RadarDistanceConfig,
+    timeout: Option<Duration>,
+    close_range_calibrated: bool,
+    strict: bool,
+    yield_during_calibration: bool,
+    radar_config_generation: u32,
+    effective_sensor_config: *const acc_config_t,
+    #[cfg(debug_assertions)]
+    measured_buffer: Option<BufferTag>,
+}
+
+/// Identifies the content of a buffer, to detect when a different buffer than the measured
+/// one is processed.
+#[cfg(debug_assertions)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct BufferTag {
+    len: usize,
+    checksum: u32,
+}
+
+#[cfg(debug_assertions)]
+impl BufferTag {
+    fn new(buffer: &[u8]) -> Self {
+        // FNV-1a
+        let checksum = buffer.iter().fold(0x811c_9dc5_u32, |hash, &byte| {
+            (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+        });
+        Self {
+            len: buffer.len(),
+            checksum,
+        }
+    }
 }
 
 impl<'radar, SINT, ENABLE, DLY> RadarDistanceDetector<'radar, SINT, ENABLE, DLY>
@@ -60,58 +155,258 @@ where
     ENABLE: OutputPin,
     DLY: DelayNs,
 {
-    /// Constructs a new radar distance detector with default configuration.
+    /// Constructs a new radar distance detector with default configuration, for the radar's
+    /// sensor.
     pub fn new(radar: &'radar mut Radar<Ready, SINT, ENABLE, DLY>) -> Self {
-        let config = RadarDistanceConfig::default();
+        let mut config = RadarDistanceConfig::default();
+        config.sensor_set(radar.id());
         let inner = InnerRadarDistanceDetector::new(&config);
         trace!("{:?}", DistanceSizes::new(&inner));
+        let radar_config_generation = radar.config.generation();
         Self {
             radar,
             inner,
             config,
+            timeout: None,
+            close_range_calibrated: false,
+            strict: false,
+            yield_during_calibration: false,
+            radar_config_generation,
+            effective_sensor_config: core::ptr::null(),
+            #[cfg(debug_assertions)]
+            measured_buffer: None,
         }
     }
 
     /// Constructs a new radar distance detector with the provided configuration.
+    ///
+    /// The sensor ID of the configuration is set to the radar's.
     pub fn with_config(
         radar: &'radar mut Radar<Ready, SINT, ENABLE, DLY>,
-        config: RadarDistanceConfig,
+        mut config: RadarDistanceConfig,
     ) -> Self {
+        warn_sensor_mismatch(radar.id(), config.sensor_get());
+        config.sensor_set(radar.id());
         let inner = InnerRadarDistanceDetector::new(&config);
         trace!("{:?}", DistanceSizes::new(&inner));
+        let radar_config_generation = radar.config.generation();
         Self {
             radar,
             inner,
             config,
+            timeout: None,
+            close_range_calibrated: false,
+            strict: false,
+            yield_during_calibration: false,
+            radar_config_generation,
+            effective_sensor_config: core::ptr::null(),
+            #[cfg(debug_assertions)]
+            measured_buffer: None,
         }
     }
 
+    /// Separates the detector from its radar, keeping its handle and state.
+    fn detach(self) -> DetachedDistanceDetector {
+        DetachedDistanceDetector {
+            inner: self.inner,
+            config: self.config,
+            timeout: self.timeout,
+            close_range_calibrated: self.close_range_calibrated,
+            strict: self.strict,
+            yield_during_calibration: self.yield_during_calibration,
+            radar_config_generation: self.radar_config_generation,
+            effective_sensor_config: self.effective_sensor_config,
+            #[cfg(debug_assertions)]
+            measured_buffer: self.measured_buffer,
+        }
+    }
+
+    /// Attaches a detached detector to `radar`, which must be the radar it was detached from.
+    fn attach(
+        radar: &'radar mut Radar<Ready, SINT, ENABLE, DLY>,
+        detached: DetachedDistanceDetector,
+    ) -> Self {
+        Self {
+            radar,
+            inner: detached.inner,
+            config: detached.config,
+            timeout: detached.timeout,
+            close_range_calibrated: detached.close_range_calibrated,
+            strict: detached.strict,
+            yield_during_calibration: detached.yield_during_calibration,
+            radar_config_generation: detached.radar_config_generation,
+            effective_sensor_config: detached.effective_sensor_config,
+            #[cfg(debug_assertions)]
+            measured_buffer: detached.measured_buffer,
+        }
+    }
+
+    /// Sets the maximum time to wait for the sensor interrupt during calibration and
+    /// measurement. `None`, the default, waits indefinitely.
+    ///
+    /// When the timeout elapses, the operation fails with `SensorError::Timeout`.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Returns the maximum time to wait for the sensor interrupt, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Enables or disables strict mode, disabled by default.
+    ///
+    /// In strict mode, [`process_data`](Self::process_data) returns
+    /// `Err(ProcessDataError::CalibrationNeeded)` when the detector reports that calibration is
+    /// needed, instead of a result with [`calibration_needed`](DistanceResult::calibration_needed)
+    /// set, and `Err(ProcessDataError::PhaseEnhancementDisabled)` without processing the data
+    /// when the programmed configuration has phase enhancement disabled, see
+    /// [`check_phase_enhancement`](Self::check_phase_enhancement).
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Returns true if strict mode is enabled.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Enables or disables yielding to the executor before each wait for the sensor during
+    /// calibration, disabled by default.
+    ///
+    /// Calibrating takes several steps, and on a cooperative executor, waiting for an interrupt
+    /// that is already asserted does not let other tasks run in between. Yielding gives them a
+    /// chance to run at each step, at the cost of a slightly longer calibration.
+    pub fn set_yield_during_calibration(&mut self, enabled: bool) {
+        self.yield_during_calibration = enabled;
+    }
+
+    /// Returns true if the detector yields to the executor during calibration.
+    pub fn yields_during_calibration(&self) -> bool {
+        self.yield_during_calibration
+    }
+
+    /// Waits for the sensor between two calibration steps, yielding first if enabled.
+    async fn wait_for_calibration_step(&mut self) -> Result<(), SensorError> {
+        if self.yield_during_calibration {
+            embassy_futures::yield_now().await;
+        }
+        self.radar.wait_for_interrupt(self.timeout).await
+    }
+
+    /// Resets the detector, discarding the recorded threshold and any other state accumulated
+    /// since calibration, while keeping the current configuration.
+    ///
+    /// The detector handle is re-created, so [`calibrate_detector`](Self::calibrate_detector)
+    /// must be called again before processing data, and previously obtained static and dynamic
+    /// calibration results must not be used with this detector anymore.
+    ///
+    /// This is also how the detector is made usable again after the radar configuration
+    /// changed, see [`check_radar_config`](Self::check_radar_config).
+    pub fn reset(&mut self) {
+        self.inner = InnerRadarDistanceDetector::new(&self.config);
+        self.close_range_calibrated = false;
+        self.radar_config_generation = self.radar.config.generation();
+        self.effective_sensor_config = core::ptr::null();
+        #[cfg(debug_assertions)]
+        {
+            self.measured_buffer = None;
+        }
+    }
+
+    /// Checks that the radar configuration did not change since the detector was created or
+    /// [`reset`](Self::reset), which would leave the detector working with a stale
+    /// configuration.
+    ///
+    /// This is checked before calibrating, preparing and measuring.
+    ///
+    /// # Returns
+    /// `Err(SensorError::NotReady)` if the radar configuration was mutated.
+    pub fn check_radar_config(&self) -> Result<(), SensorError> {
+        check_config_generation(&self.radar.config, self.radar_config_generation)
+    }
+
+    /// Checks that phase enhancement is enabled in every subsweep of the sensor configuration
+    /// programmed by the detector, see [`effective_sensor_config`](Self::effective_sensor_config),
+    /// which the coherent filtering of the detector relies on. Without it, the results are
+    /// silently degraded.
+    ///
+    /// The programmed configuration is only known once data was processed, until then the
+    /// check passes. [`process_data`](Self::process_data) flags its results with
+    /// [`DistanceResult::phase_enhancement_disabled`] and, in strict mode, checks the
+    /// configuration known from the previous result before processing the data.
+    ///
+    /// # Returns
+    /// `Err(SensorError::PhaseEnhancementDisabled)` if phase enhancement is disabled and strict
+    /// mode is enabled.
+    pub fn check_phase_enhancement(&self) -> Result<(), SensorError> {
+        if self.phase_enhancement_enabled() != Some(false) {
+            return Ok(());
+        }
+        if self.strict {
+            return Err(SensorError::PhaseEnhancementDisabled);
+        }
+        defmt::warn!("Phase enhancement is disabled, coherent filtering will be degraded");
+        Ok(())
+    }
+
+    /// Returns whether phase enhancement is enabled in the programmed configuration, or `None`
+    /// if it is not known yet.
+    fn phase_enhancement_enabled(&self) -> Option<bool> {
+        let config = self.effective_sensor_config()?;
+        Some(all_subsweeps_enabled(config.subsweeps().map(|subsweep| {
+            subsweep.is_phase_enhancement_enabled(&config)
+        })))
+    }
+
     /// Performs calibration of the radar distance detector.
+    ///
+    /// When close range leakage cancellation is enabled, this also calibrates the close range,
+    /// which [`update_calibration`](Self::update_calibration) does not redo.
     pub async fn calibrate_detector(
         &mut self,
         sensor_cal_result: &CalibrationResult,
         buffer: &mut [u8],
         detector_cal_result_static: &mut [u8],
     ) -> Result<DynamicResult, SensorError> {
+        self.calibrate_detector_into(
+            sensor_cal_result,
+            CalibrationBuffer::Caller(buffer),
+            detector_cal_result_static,
+        )
+        .await
+    }
+
+    /// Calibrates the detector, using `buffer` as working memory.
+    async fn calibrate_detector_into(
+        &mut self,
+        sensor_cal_result: &CalibrationResult,
+        mut buffer: CalibrationBuffer<'_>,
+        detector_cal_result_static: &mut [u8],
+    ) -> Result<DynamicResult, SensorError> {
+        self.check_radar_config()?;
+        self.radar.check_idle()?;
         let mut calibration_complete: bool = false;
         let mut detector_cal_result_dynamic = DynamicResult::default();
         let distances = DistanceSizes::new(&self.inner);
+        self.close_range_calibrated = false;
 
         // Check buffer sizes before attempting calibration
-        if buffer.len() < distances.buffer_size
-            || detector_cal_result_static.len() < distances.detector_cal_result_static_size
-        {
-            return Err(SensorError::BufferTooSmall);
-        }
+        distances.check_buffers(
+            buffer.get(self.radar).len(),
+            detector_cal_result_static.len(),
+        )?;
 
         loop {
+            let sensor = self.radar.inner_sensor();
+            let working = buffer.get(self.radar);
             let calibration_attempt = unsafe {
                 acc_detector_distance_calibrate(
-                    self.radar.inner_sensor(),
+                    sensor,
                     self.inner.inner_mut(),
                     sensor_cal_result.ptr(),
-                    buffer.as_mut_ptr() as *mut c_void,
-                    buffer.len() as u32,
+                    working.as_mut_ptr() as *mut c_void,
+                    working.len() as u32,
                     detector_cal_result_static.as_mut_ptr(),
                     detector_cal_result_static.len() as u32,
                     &mut detector_cal_result_dynamic.inner
@@ -131,16 +426,56 @@ where
             }
 
             // Wait for the interrupt signal asynchronously
-            self.radar
-                .interrupt
-                .wait_for_high()
-                .await
-                .expect("Failed to wait for interrupt");
+            self.wait_for_calibration_step().await?;
         }
 
+        self.close_range_calibrated = self.config.close_range_leakage_cancelation();
         Ok(detector_cal_result_dynamic)
     }
 
+    /// Returns true if the last detector calibration succeeded with close range leakage
+    /// cancellation enabled.
+    ///
+    /// If close range leakage cancellation is enabled after calibrating, this returns false
+    /// and [`calibrate_detector`](Self::calibrate_detector) must be called again.
+    pub fn is_close_range_calibrated(&self) -> bool {
+        self.close_range_calibrated && self.config.close_range_leakage_cancelation()
+    }
+
+    /// Performs calibration of the radar distance detector, using the radar's scratch buffer
+    /// as working memory instead of a caller-provided buffer.
+    ///
+    /// Returns `Err(SensorError::BufferTooSmall)` if the scratch buffer is too small for the
+    /// current detector configuration.
+    pub async fn calibrate_detector_using_scratch(
+        &mut self,
+        sensor_cal_result: &CalibrationResult,
+        detector_cal_result_static: &mut [u8],
+    ) -> Result<DynamicResult, SensorError> {
+        self.calibrate_detector_into(
+            sensor_cal_result,
+            CalibrationBuffer::Scratch,
+            detector_cal_result_static,
+        )
+        .await
+    }
+
+    #[cfg(not(feature = "no-alloc"))]
+    /// Performs calibration of the radar distance detector using the given buffers.
+    ///
+    /// The dynamic calibration result is stored in `buffers`.
+    pub async fn calibrate_detector_with_buffers(
+        &mut self,
+        sensor_cal_result: &CalibrationResult,
+        buffers: &mut DistanceBuffers,
+    ) -> Result<(), SensorError> {
+        let (buffer, static_cal_result, dynamic_cal_result) = buffers.split_mut();
+        *dynamic_cal_result = self
+            .calibrate_detector(sensor_cal_result, buffer, static_cal_result)
+            .await?;
+        Ok(())
+    }
+
     /// Returns the size of the buffer needed for static calibration results.
     pub fn get_static_result_buffer_size(&self) -> usize {
         DistanceSizes::new(&self.inner).detector_cal_result_static_size
@@ -151,6 +486,50 @@ where
         DistanceSizes::new(&self.inner).buffer_size
     }
 
+    #[cfg(not(feature = "no-alloc"))]
+    /// Allocates zeroed buffers sized for the current configuration.
+    ///
+    /// # Returns
+    /// The working buffer, the static calibration result buffer and a default dynamic
+    /// calibration result, in the order expected by
+    /// [`calibrate_detector`](Self::calibrate_detector).
+    pub fn allocate_buffers(&self) -> (Vec<u8>, Vec<u8>, DynamicResult) {
+        let sizes = DistanceSizes::new(&self.inner);
+        (
+            vec![0; sizes.buffer_size],
+            vec![0; sizes.detector_cal_result_static_size],
+            DynamicResult::default(),
+        )
+    }
+
+    /// Returns the memory needed by the detector with its current configuration.
+    ///
+    /// The sizes are the ones the SDK demands, so this can be used to size buffers before
+    /// allocating them. The buffer size is also estimated with a [`DistanceMemoryCalculator`],
+    /// and the report flags the SDK demanding more than estimated.
+    pub fn memory_report(&self) -> MemoryReport {
+        let sizes = DistanceSizes::new(&self.inner);
+        MemoryReport {
+            buffer_size: sizes.buffer_size,
+            estimated_buffer_size: DistanceMemoryCalculator::from_config(&self.config)
+                .buffer_size(),
+            static_cal_result_size: sizes.detector_cal_result_static_size,
+            scratch_size: self.radar.scratch.len(),
+        }
+    }
+
+    /// Returns the step length, in points, of the first subsweep of the sensor configuration
+    /// programmed by the detector, see [`effective_sensor_config`](Self::effective_sensor_config).
+    ///
+    /// When the maximum step length is [`MaxStepLenght::ProfileBased`](config::MaxStepLenght),
+    /// this is the step length selected by the detector. `None` is returned until data was
+    /// processed.
+    pub fn effective_step_length(&self) -> Option<u16> {
+        let config = self.effective_sensor_config()?;
+        let subsweep = config.subsweeps().next()?;
+        Some(subsweep.step_length(&config))
+    }
+
     /// Updates the calibration dynamically based on new sensor data.
     /// This function is intended to be used when a recalibration is necessary due to changes in the operating environment.
     pub async fn update_calibration(
@@ -158,6 +537,8 @@ where
         sensor_cal_result: &CalibrationResult,
         buffer: &mut [u8],
     ) -> Result<DynamicResult, SensorError> {
+        self.check_radar_config()?;
+        self.radar.check_idle()?;
         let mut calibration_complete: bool = false;
         let mut detector_cal_result_dynamic = DynamicResult::default();
         let calibration_attempt: bool;
@@ -177,11 +558,7 @@ where
         if calibration_attempt {
             while !calibration_complete {
                 // Wait for the interrupt to occur asynchronously
-                self.radar
-                    .interrupt
-                    .wait_for_high()
-                    .await
-                    .expect("Failed to wait for interrupt");
+                self.wait_for_calibration_step().await?;
                 unsafe {
                     acc_detector_distance_update_calibration(
                         self.radar.inner_sensor(),
@@ -202,6 +579,57 @@ where
         }
     }
 
+    #[cfg(not(feature = "no-alloc"))]
+    /// Updates the dynamic calibration stored in `buffers`.
+    pub async fn update_calibration_with_buffers(
+        &mut self,
+        sensor_cal_result: &CalibrationResult,
+        buffers: &mut DistanceBuffers,
+    ) -> Result<(), SensorError> {
+        let (buffer, _, dynamic_cal_result) = buffers.split_mut();
+        *dynamic_cal_result = self.update_calibration(sensor_cal_result, buffer).await?;
+        Ok(())
+    }
+
+    /// Recovers from a persistent need for calibration.
+    ///
+    /// Calibrates the sensor again, which resets it first, storing the new result in
+    /// `sensor_cal_result`, then calibrates and prepares the detector, leaving it ready to
+    /// measure.
+    ///
+    /// # Returns
+    /// The new dynamic calibration result of the detector.
+    pub async fn recover(
+        &mut self,
+        sensor_cal_result: &mut CalibrationResult,
+        buffer: &mut [u8],
+        detector_cal_result_static: &mut [u8],
+    ) -> Result<DynamicResult, SensorError> {
+        *sensor_cal_result = self.radar.calibrate().await?;
+        let detector_cal_result_dynamic = self
+            .calibrate_detector(sensor_cal_result, buffer, detector_cal_result_static)
+            .await?;
+        self.prepare_detector(sensor_cal_result, buffer)?;
+        Ok(detector_cal_result_dynamic)
+    }
+
+    #[cfg(not(feature = "no-alloc"))]
+    /// Recovers from a persistent need for calibration using the given buffers, see
+    /// [`recover`](Self::recover).
+    ///
+    /// The dynamic calibration result is stored in `buffers`.
+    pub async fn recover_with_buffers(
+        &mut self,
+        sensor_cal_result: &mut CalibrationResult,
+        buffers: &mut DistanceBuffers,
+    ) -> Result<(), SensorError> {
+        let (buffer, static_cal_result, dynamic_cal_result) = buffers.split_mut();
+        *dynamic_cal_result = self
+            .recover(sensor_cal_result, buffer, static_cal_result)
+            .await?;
+        Ok(())
+    }
+
     /// Prepares the detector for a measurement operation.
     ///
     /// This function must be called before performing a distance measurement to configure the detector properly.
@@ -210,6 +638,8 @@ where
         sensor_cal_result: &CalibrationResult,
         buffer: &mut [u8],
     ) -> Result<(), SensorError> {
+        self.radar.check_idle()?;
+        self.check_radar_config()?;
         unsafe {
             if acc_detector_distance_prepare(
                 self.inner.inner(),
@@ -229,8 +659,21 @@ where
     /// Performs a distance measurement operation asynchronously.
     ///
     /// This function initiates a measurement operation, returning the results asynchronously.
+    ///
+    /// In debug builds, the content of `data` is recorded so that
+    /// [`process_data`](Self::process_data) can detect when it is given another buffer.
     pub async fn measure(&mut self, data: &mut [u8]) -> Result<(), SensorError> {
-        self.radar.measure(data).await
+        self.check_radar_config()?;
+        #[cfg(debug_assertions)]
+        {
+            self.measured_buffer = None;
+        }
+        self.radar.measure_inner(data, self.timeout).await?;
+        #[cfg(debug_assertions)]
+        {
+            self.measured_buffer = Some(BufferTag::new(data));
+        }
+        Ok(())
     }
 
     /// Calibrates the associated radar asynchronously.
@@ -243,12 +686,30 @@ where
     /// Processes the data collected from a distance measurement operation.
     ///
     /// This function analyzes the raw data collected during a measurement operation, extracting distance information.
+    ///
+    /// In debug builds, returns `Err(ProcessDataError::StaleBuffer)` if `buffer` does not hold
+    /// the data of the last [`measure`](Self::measure). Phase enhancement is checked in the
+    /// programmed sensor configuration, see
+    /// [`check_phase_enhancement`](Self::check_phase_enhancement), and reported by
+    /// [`DistanceResult::phase_enhancement_disabled`].
     pub fn process_data(
         &mut self,
         buffer: &mut [u8],
         detector_cal_result_static: &mut [u8],
         detector_cal_result_dynamic: &mut DynamicResult,
     ) -> Result<DistanceResult<'_>, ProcessDataError> {
+        #[cfg(debug_assertions)]
+        if let Some(tag) = self.measured_buffer.take() {
+            if tag != BufferTag::new(buffer) {
+                return Err(ProcessDataError::StaleBuffer);
+            }
+        }
+
+        // The detector consumes the data, so reject it in strict mode before processing
+        if self.strict && self.phase_enhancement_enabled() == Some(false) {
+            return Err(ProcessDataError::PhaseEnhancementDisabled);
+        }
+
         let mut result_available: bool = false;
         let mut distance_result = DistanceResult::new(&self.radar.config);
         let mut distance_result_ptr: acc_detector_distance_result_t = distance_result.inner();
@@ -263,10 +724,22 @@ where
                 &mut distance_result_ptr as *mut acc_detector_distance_result_t,
             )
         };
+        if process_attempt && !distance_result_ptr.sensor_config.is_null() {
+            let first = self.effective_sensor_config != distance_result_ptr.sensor_config;
+            self.effective_sensor_config = distance_result_ptr.sensor_config;
+            let disabled = self.phase_enhancement_enabled() == Some(false);
+            distance_result.set_phase_enhancement_disabled(disabled);
+            // Warn once per programmed configuration
+            if first && disabled {
+                defmt::warn!("Phase enhancement is disabled, coherent filtering will be degraded");
+            }
+        }
         distance_result.update_from_detector_result(distance_result_ptr);
 
         if process_attempt {
-            if result_available {
+            if self.strict && distance_result.calibration_needed() {
+                Err(ProcessDataError::CalibrationNeeded)
+            } else if result_available {
                 Ok(distance_result)
             } else {
                 Err(ProcessDataError::Unavailable)
@@ -276,8 +749,169 @@ where
         }
     }
 
+    /// Returns the sensor configuration actually programmed by the detector, which is derived
+    /// from the detector configuration and differs from the radar configuration, e.g. in its
+    /// number of points.
+    ///
+    /// The configuration is the one reported with the last result processed by
+    /// [`process_data`](Self::process_data), so `None` is returned until data was processed.
+    pub fn effective_sensor_config(&self) -> Option<RadarConfigRef<'_>> {
+        if self.effective_sensor_config.is_null() {
+            None
+        } else {
+            // The configuration is owned by the detector handle, which outlives the borrow
+            Some(unsafe { RadarConfig::from_raw_borrowed(self.effective_sensor_config) })
+        }
+    }
+
+    #[cfg(not(feature = "no-alloc"))]
+    /// Processes the data measured into `buffers`, using the calibration results it holds.
+    pub fn process_data_with_buffers(
+        &mut self,
+        buffers: &mut DistanceBuffers,
+    ) -> Result<DistanceResult<'_>, ProcessDataError> {
+        let (buffer, static_cal_result, dynamic_cal_result) = buffers.split_mut();
+        self.process_data(buffer, static_cal_result, dynamic_cal_result)
+    }
+
+    #[cfg(not(feature = "no-alloc"))]
+    /// Prepares the detector, measures and processes the data, returning the detected distances.
+    ///
+    /// The detector must have been calibrated into `buffers` with
+    /// [`calibrate_detector_with_buffers`](Self::calibrate_detector_with_buffers). When
+    /// calibration is needed, the sensor is calibrated again, storing the new result in
+    /// `sensor_cal_result`, the detector calibration is updated and the measurement is retried
+    /// once.
+    ///
+    /// # Returns
+    /// The detected distances, also available from [`DistanceBuffers::distances`], or
+    /// `Err(SensorError::CalibrationInvalid)` if calibration is still needed after recalibrating,
+    /// or `Err(SensorError::StaleBuffer)` in debug builds if the processed buffer is not the
+    /// measured one.
+    pub async fn next_distances<'b>(
+        &mut self,
+        sensor_cal_result: &mut CalibrationResult,
+        buffers: &'b mut DistanceBuffers,
+    ) -> Result<&'b [results::Distance], SensorError> {
+        for attempt in 0..2 {
+            self.prepare_detector(sensor_cal_result, buffers.buffer_mut())?;
+            self.measure(buffers.buffer_mut()).await?;
+            let calibration_needed = match self.process_data_with_buffers(buffers) {
+                Ok(result) if result.calibration_needed() => true,
+                Ok(result) => {
+                    let distances = result.distances();
+                    buffers.set_distances(distances);
+                    false
+                }
+                Err(ProcessDataError::CalibrationNeeded) => true,
+                Err(ProcessDataError::ProcessingFailed) => {
+                    return Err(SensorError::ProcessingFailed)
+                }
+                Err(ProcessDataError::Unavailable) => return Err(SensorError::ResultNotAvailable),
+                Err(ProcessDataError::StaleBuffer) => return Err(SensorError::StaleBuffer),
+                Err(ProcessDataError::PhaseEnhancementDisabled) => {
+                    return Err(SensorError::PhaseEnhancementDisabled)
+                }
+            };
+            if !calibration_needed {
+                return Ok(buffers.distances());
+            }
+            if attempt == 0 {
+                *sensor_cal_result = self.radar.calibrate().await?;
+                self.update_calibration_with_buffers(sensor_cal_result, buffers)
+                    .await?;
+            }
+        }
+        Err(SensorError::CalibrationInvalid)
+    }
+
+    #[cfg(not(feature = "no-alloc"))]
+    /// Measures and processes the data like [`measure_once`](Detector::measure_once), returning
+    /// a result that does not borrow the detector, e.g. to send it through a channel.
+    pub async fn measure_owned(
+        &mut self,
+        buffers: &mut DistanceBuffers,
+    ) -> Result<results::OwnedDistanceResult, SensorError> {
+        self.measure_once(buffers)
+            .await
+            .map(|result| result.to_owned())
+    }
+
     /// Prints the status of the radar distance detector.
     pub fn print_status(&mut self) {
-        self.radar.check_status()
+        self.radar.log_status()
+    }
+}
+
+#[cfg(not(feature = "no-alloc"))]
+impl<'radar, SINT, ENABLE, DLY> Detector for RadarDistanceDetector<'radar, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    /// Buffers holding the detector calibration, prepared with
+    /// [`prepare_detector`](RadarDistanceDetector::prepare_detector)
+    type Buffers = DistanceBuffers;
+    type Result<'a>
+        = DistanceResult<'a>
+    where
+        Self: 'a;
+
+    async fn measure_once(
+        &mut self,
+        buffers: &mut Self::Buffers,
+    ) -> Result<Self::Result<'_>, SensorError> {
+        self.measure(buffers.buffer_mut()).await?;
+        self.process_data_with_buffers(buffers)
+            .map_err(|error| match error {
+                ProcessDataError::CalibrationNeeded => SensorError::CalibrationInvalid,
+                ProcessDataError::ProcessingFailed => SensorError::ProcessingFailed,
+                ProcessDataError::Unavailable => SensorError::ResultNotAvailable,
+                ProcessDataError::StaleBuffer => SensorError::StaleBuffer,
+                ProcessDataError::PhaseEnhancementDisabled => SensorError::PhaseEnhancementDisabled,
+            })
+    }
+}
+
+/// Returns true if phase enhancement is enabled in every subsweep, given the setting of each.
+fn all_subsweeps_enabled(phase_enhancement: impl IntoIterator<Item = bool>) -> bool {
+    phase_enhancement.into_iter().all(|enabled| enabled)
+}
+
+/// Checks that `config` was not mutated since its generation was `recorded`.
+fn check_config_generation(config: &RadarConfig, recorded: u32) -> Result<(), SensorError> {
+    if config.generation() == recorded {
+        Ok(())
+    } else {
+        Err(SensorError::NotReady)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutating_the_radar_config_invalidates_the_detector() {
+        let mut config = RadarConfig::detached();
+        let recorded = config.generation();
+        assert!(check_config_generation(&config, recorded).is_ok());
+
+        config.mark_changed();
+        assert!(matches!(
+            check_config_generation(&config, recorded),
+            Err(SensorError::NotReady)
+        ));
+        assert!(check_config_generation(&config, config.generation()).is_ok());
+    }
+
+    #[test]
+    fn phase_enhancement_must_be_enabled_in_every_subsweep() {
+        assert!(all_subsweeps_enabled([true]));
+        assert!(all_subsweeps_enabled([true, true, true]));
+        assert!(!all_subsweeps_enabled([false]));
+        assert!(!all_subsweeps_enabled([true, false, true]));
+        assert!(!all_subsweeps_enabled([true, true, false]));
     }
 }
@@ -0,0 +1,78 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::detector::distance::results::{Distance, DistanceSizes, DynamicResult};
+use crate::detector::distance::RadarDistanceDetector;
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+
+/// Holds all the buffers needed by a distance detector, sized for its configuration.
+///
+/// This avoids juggling the working buffer and the static and dynamic calibration results
+/// separately, and guarantees they are large enough for the detector they were created for.
+pub struct DistanceBuffers {
+    buffer: Vec<u8>,
+    static_cal_result: Vec<u8>,
+    dynamic_cal_result: DynamicResult,
+    distances: Vec<Distance>,
+}
+
+impl DistanceBuffers {
+    /// Allocates buffers sized for the given detector.
+    pub fn new<SINT, ENABLE, DLY>(detector: &RadarDistanceDetector<'_, SINT, ENABLE, DLY>) -> Self
+    where
+        SINT: Wait,
+        ENABLE: OutputPin,
+        DLY: DelayNs,
+    {
+        let sizes = DistanceSizes::new(&detector.inner);
+        Self {
+            buffer: vec![0; sizes.buffer_size],
+            static_cal_result: vec![0; sizes.detector_cal_result_static_size],
+            dynamic_cal_result: DynamicResult::default(),
+            distances: Vec::new(),
+        }
+    }
+
+    /// Returns the working buffer, used for measurements and processing.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Returns the working buffer mutably, used for measurements and processing.
+    pub fn buffer_mut(&mut self) -> &mut [u8] {
+        &mut self.buffer
+    }
+
+    /// Returns the static part of the detector calibration result.
+    pub fn static_cal_result(&self) -> &[u8] {
+        &self.static_cal_result
+    }
+
+    /// Returns the dynamic part of the detector calibration result.
+    pub fn dynamic_cal_result(&self) -> &DynamicResult {
+        &self.dynamic_cal_result
+    }
+
+    /// Returns the distances detected by the last
+    /// [`next_distances`](RadarDistanceDetector::next_distances).
+    pub fn distances(&self) -> &[Distance] {
+        &self.distances
+    }
+
+    pub(super) fn set_distances(&mut self, distances: &[Distance]) {
+        self.distances.clear();
+        self.distances.extend_from_slice(distances);
+    }
+
+    /// Splits the buffers into the working buffer, the static calibration result and the
+    /// dynamic calibration result.
+    pub fn split_mut(&mut self) -> (&mut [u8], &mut [u8], &mut DynamicResult) {
+        (
+            &mut self.buffer,
+            &mut self.static_cal_result,
+            &mut self.dynamic_cal_result,
+        )
+    }
+}
@@ -8,6 +8,9 @@
 
 #![warn(missing_docs)]
 
+use crate::config::error::ConfigError;
+use crate::config::fingerprint::Fingerprint;
+use crate::config::prf::PulseRepetitionFrequency;
 use crate::config::profile::RadarProfile;
 use crate::config::profile::RadarProfile::AccProfile5;
 use a121_sys::*;
@@ -19,7 +22,7 @@ pub type SignalQuality = f32;
 pub type ThresholdSensitivity = f32;
 
 /// Enum representing the reflector shape
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
 pub enum ReflectorShape {
     /// Generic reflector shape
     /// This is the default value and represents any non liquid reflector
@@ -42,7 +45,27 @@ impl From<u32> for ReflectorShape {
     }
 }
 
+impl ReflectorShape {
+    /// Approximate strength offset, in dB, of this shape relative to [`ReflectorShape::Generic`]
+    ///
+    /// Planar reflectors return considerably more energy than generic ones at the same distance,
+    /// so their strengths are shifted down by this amount to make them comparable.
+    ///
+    /// The radar equation spreads the energy returned by a point-like target over `R^4`, but
+    /// the energy returned by a large planar surface, such as a liquid level, only over `R^2`.
+    /// At a distance `R` the planar reflector is thus `20 * log10(R)` dB stronger; the planar
+    /// offset is this difference evaluated at 10 m, in the upper part of the detector range.
+    /// It is a rough constant and overestimates the offset at shorter distances.
+    pub const fn strength_offset_db(&self) -> f32 {
+        match self {
+            Self::Generic => 0.0,
+            Self::Planar => 20.0,
+        }
+    }
+}
+
 /// Enum representing the maximum step length
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MaxStepLenght {
     /// Uses the step length based on the profile
     ProfileBased,
@@ -96,6 +119,42 @@ impl Drop for RadarDistanceConfig {
     }
 }
 
+impl Clone for RadarDistanceConfig {
+    /// Creates a new configuration with the same settings.
+    fn clone(&self) -> Self {
+        let mut config = Self::new();
+        config.sensor_set(self.sensor_get());
+        config.set_start_interval(self.start_interval());
+        config.set_end_interval(self.end_interval());
+        config
+            .set_max_step_length(self.max_step_length_mode())
+            .unwrap();
+        config.set_close_range_leakage_cancelation(self.close_range_leakage_cancelation());
+        config.set_signal_quality(self.signal_quality());
+        config.set_max_profile(self.max_profile());
+        config.set_threshold_method(self.threshold_method());
+        // Copy the parameters of the threshold methods not in use as well
+        unsafe {
+            acc_detector_distance_config_fixed_amplitude_threshold_value_set(
+                config.inner,
+                acc_detector_distance_config_fixed_amplitude_threshold_value_get(self.inner),
+            );
+            acc_detector_distance_config_fixed_strength_threshold_value_set(
+                config.inner,
+                acc_detector_distance_config_fixed_strength_threshold_value_get(self.inner),
+            );
+            acc_detector_distance_config_num_frames_recorded_threshold_set(
+                config.inner,
+                acc_detector_distance_config_num_frames_recorded_threshold_get(self.inner),
+            );
+        }
+        config.set_threshold_sensitivity(self.threshold_sensitivity());
+        config.set_peak_sorting_method(self.peak_sorting_method());
+        config.set_reflector_shape(self.reflector_shape());
+        config
+    }
+}
+
 impl Default for RadarDistanceConfig {
     fn default() -> Self {
         Self::balanced()
@@ -113,8 +172,10 @@ impl RadarDistanceConfig {
     /// Create a balanced distance detection configuration.
     pub fn balanced() -> Self {
         let mut config = Self::new();
-        config.set_interval(15.0..=17.0);
-        config.set_max_step_length(MaxStepLenght::ProfileBased);
+        config.set_interval(15.0..=17.0).unwrap();
+        config
+            .set_max_step_length(MaxStepLenght::ProfileBased)
+            .unwrap();
         config.set_max_profile(AccProfile5);
         config.set_reflector_shape(ReflectorShape::Generic);
         config.set_peak_sorting_method(PeakSortingMethod::Strength);
@@ -130,10 +191,24 @@ impl RadarDistanceConfig {
         unsafe { acc_detector_distance_config_sensor_set(self.inner, sensor_id) }
     }
 
+    /// Returns the sensor ID used for detection.
+    pub fn sensor_get(&self) -> u32 {
+        unsafe { acc_detector_distance_config_sensor_get(self.inner) }
+    }
+
     /// Configures the measurement interval in meters.
-    pub fn set_interval(&mut self, range: RangeInclusive<f32>) {
-        self.set_start_interval(*range.start());
-        self.set_end_interval(*range.end());
+    ///
+    /// Returns `Err(ConfigError::Interval)` if the start is negative, the end is before the
+    /// start, or the end is beyond the maximum measurable distance of the lowest PRF.
+    pub fn set_interval(&mut self, range: RangeInclusive<f32>) -> Result<(), ConfigError> {
+        let (start, end) = (*range.start(), *range.end());
+        let max_distance = PulseRepetitionFrequency::Prf5_2Mhz.max_measurable_distance();
+        if !(0.0..=end).contains(&start) || end > max_distance {
+            return Err(ConfigError::Interval);
+        }
+        self.set_start_interval(start);
+        self.set_end_interval(end);
+        Ok(())
     }
 
     /// Sets the start of the measurement interval in meters.
@@ -158,15 +233,23 @@ impl RadarDistanceConfig {
 
     /// Sets the maximum step length in points.
     /// Using a manual maximum step length can have a big impact on memory usage and performance.
-    pub fn set_max_step_length(&mut self, max_step_length: MaxStepLenght) {
+    ///
+    /// Returns `Err(ConfigError::MaxStepLength)` for `MaxStepLenght::Manual(0)`, which the SDK
+    /// would interpret as profile based.
+    pub fn set_max_step_length(
+        &mut self,
+        max_step_length: MaxStepLenght,
+    ) -> Result<(), ConfigError> {
         match max_step_length {
             MaxStepLenght::ProfileBased => unsafe {
                 acc_detector_distance_config_max_step_length_set(self.inner, 0)
             },
+            MaxStepLenght::Manual(0) => return Err(ConfigError::MaxStepLength),
             MaxStepLenght::Manual(length) => unsafe {
                 acc_detector_distance_config_max_step_length_set(self.inner, length)
             },
         }
+        Ok(())
     }
 
     /// Returns the maximum step length in points.
@@ -174,6 +257,14 @@ impl RadarDistanceConfig {
         unsafe { acc_detector_distance_config_max_step_length_get(self.inner) }
     }
 
+    /// Returns the maximum step length mode.
+    pub fn max_step_length_mode(&self) -> MaxStepLenght {
+        match self.max_step_length() {
+            0 => MaxStepLenght::ProfileBased,
+            length => MaxStepLenght::Manual(length),
+        }
+    }
+
     /// Enable or disable close range leakage cancellation.
     /// This feature is used to cancel out the leakage from the close range (< 100mm from the sensor).
     pub fn set_close_range_leakage_cancelation(&mut self, enable: bool) {
@@ -204,7 +295,7 @@ impl RadarDistanceConfig {
 
     /// Sets the maximum profile to use.
     pub fn set_max_profile(&mut self, max_profile: RadarProfile) {
-        unsafe { acc_detector_distance_config_max_profile_set(self.inner, max_profile as u32) }
+        unsafe { acc_detector_distance_config_max_profile_set(self.inner, max_profile.to_ffi()) }
     }
 
     /// Returns the maximum profile to use.
@@ -285,4 +376,31 @@ impl RadarDistanceConfig {
     pub fn reflector_shape(&self) -> ReflectorShape {
         unsafe { acc_detector_distance_config_reflector_shape_get(self.inner) }.into()
     }
+
+    /// Returns a fingerprint of all the settings of the configuration.
+    ///
+    /// Configurations with the same settings have the same fingerprint, which is stable across
+    /// runs and platforms, so it can be used as a key for cached calibrations.
+    pub fn fingerprint(&self) -> u64 {
+        let mut fingerprint = Fingerprint::new();
+        fingerprint
+            .u32(self.sensor_get())
+            .f32(self.start_interval())
+            .f32(self.end_interval())
+            .u32(self.max_step_length() as u32)
+            .bool(self.close_range_leakage_cancelation())
+            .f32(self.signal_quality())
+            .u32(self.max_profile().to_ffi());
+        match self.threshold_method() {
+            ThresholdMethod::FixedAmplitude(amplitude) => fingerprint.u32(0).f32(amplitude),
+            ThresholdMethod::FixedStrenght(strength) => fingerprint.u32(1).f32(strength),
+            ThresholdMethod::Recorded(num_frames) => fingerprint.u32(2).u32(num_frames as u32),
+            ThresholdMethod::Cfar => fingerprint.u32(3),
+        };
+        fingerprint
+            .f32(self.threshold_sensitivity())
+            .u32(self.peak_sorting_method() as u32)
+            .u32(self.reflector_shape() as u32);
+        fingerprint.finish()
+    }
 }
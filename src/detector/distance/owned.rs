@@ -0,0 +1,128 @@
+use crate::detector::distance::config::RadarDistanceConfig;
+use crate::detector::distance::{DetachedDistanceDetector, RadarDistanceDetector};
+use crate::detector::DetachedSlot;
+use crate::radar::{Radar, Ready};
+use core::mem::ManuallyDrop;
+use core::ops::{Deref, DerefMut};
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+
+/// A distance detector that owns its radar.
+///
+/// Created with [`Radar::into_distance_detector`], it can be stored without borrowing the
+/// radar. The detector handle and its state, e.g. calibration, are kept while the radar is
+/// lent to a [`RadarDistanceDetector`] by [`detector`](Self::detector). Use
+/// [`release`](Self::release) to get the radar back.
+pub struct OwnedDistanceDetector<SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    detached: DetachedSlot<DetachedDistanceDetector>,
+    radar: Radar<Ready, SINT, ENABLE, DLY>,
+}
+
+impl<SINT, ENABLE, DLY> OwnedDistanceDetector<SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    fn new(mut radar: Radar<Ready, SINT, ENABLE, DLY>, config: RadarDistanceConfig) -> Self {
+        let detached = RadarDistanceDetector::with_config(&mut radar, config).detach();
+        Self {
+            detached: DetachedSlot::new(detached),
+            radar,
+        }
+    }
+
+    /// Returns the detector, borrowing the owned radar.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a previously returned [`DistanceDetectorRef`] was leaked instead of dropped,
+    /// which loses the detector state.
+    pub fn detector(&mut self) -> DistanceDetectorRef<'_, SINT, ENABLE, DLY> {
+        let detached = self.detached.take();
+        DistanceDetectorRef {
+            detector: ManuallyDrop::new(RadarDistanceDetector::attach(&mut self.radar, detached)),
+            detached: &mut self.detached,
+        }
+    }
+
+    /// Destroys the detector and returns the radar it owned.
+    pub fn release(self) -> Radar<Ready, SINT, ENABLE, DLY> {
+        self.radar
+    }
+}
+
+/// A [`RadarDistanceDetector`] borrowing the radar of an [`OwnedDistanceDetector`], returning
+/// the detector state to it when dropped.
+pub struct DistanceDetectorRef<'a, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    detector: ManuallyDrop<RadarDistanceDetector<'a, SINT, ENABLE, DLY>>,
+    detached: &'a mut DetachedSlot<DetachedDistanceDetector>,
+}
+
+impl<'a, SINT, ENABLE, DLY> Deref for DistanceDetectorRef<'a, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    type Target = RadarDistanceDetector<'a, SINT, ENABLE, DLY>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.detector
+    }
+}
+
+impl<SINT, ENABLE, DLY> DerefMut for DistanceDetectorRef<'_, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.detector
+    }
+}
+
+impl<SINT, ENABLE, DLY> Drop for DistanceDetectorRef<'_, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    fn drop(&mut self) {
+        // The detector is not used after being taken
+        let detector = unsafe { ManuallyDrop::take(&mut self.detector) };
+        self.detached.put(detector.detach());
+    }
+}
+
+impl<SINT, ENABLE, DLY> Radar<Ready, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    /// Moves the radar into a distance detector with default configuration.
+    pub fn into_distance_detector(self) -> OwnedDistanceDetector<SINT, ENABLE, DLY> {
+        OwnedDistanceDetector::new(self, RadarDistanceConfig::default())
+    }
+
+    /// Moves the radar into a distance detector with the provided configuration.
+    pub fn into_distance_detector_with_config(
+        self,
+        config: RadarDistanceConfig,
+    ) -> OwnedDistanceDetector<SINT, ENABLE, DLY> {
+        OwnedDistanceDetector::new(self, config)
+    }
+}
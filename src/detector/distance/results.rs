@@ -1,11 +1,16 @@
 use crate::config::RadarConfig;
+use crate::detector::distance::config::{RadarDistanceConfig, ReflectorShape};
 use crate::detector::distance::InnerRadarDistanceDetector;
+use crate::hal::aligned_buffer_size;
 use crate::processing::metadata::ProcessingMetaData;
 use crate::processing::ProcessingResult;
+use crate::sensor::error::SensorError;
 use a121_sys::{
     acc_detector_cal_result_dynamic_t, acc_detector_distance_get_sizes,
     acc_detector_distance_result_t, ACC_DETECTOR_DISTANCE_RESULT_MAX_NUM_DISTANCES,
 };
+use core::fmt::{Display, Formatter};
+use defmt::warn;
 
 /// Enumerates possible errors that can occur during the processing of radar data.
 #[derive(Debug, Copy, Clone, defmt::Format)]
@@ -13,6 +18,8 @@ pub enum ProcessDataError {
     CalibrationNeeded,
     ProcessingFailed,
     Unavailable,
+    StaleBuffer,
+    PhaseEnhancementDisabled,
 }
 
 /// Represents a single detected distance and its strength.
@@ -22,6 +29,55 @@ pub struct Distance {
     pub strength: f32,
 }
 
+impl Display for Distance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:.2} m @ {:.1} dB", self.distance, self.strength)
+    }
+}
+
+impl Distance {
+    /// Returns the strength normalized to the [`ReflectorShape::Generic`] scale.
+    ///
+    /// # Arguments
+    /// * `shape` - The reflector shape the detector was configured with
+    ///
+    /// # Returns
+    /// The strength minus [`ReflectorShape::strength_offset_db`] of `shape`.
+    pub fn normalized_strength(&self, shape: ReflectorShape) -> f32 {
+        self.strength - shape.strength_offset_db()
+    }
+}
+
+/// Maximum distance, in meters, of the strongest peak for an object to be considered as
+/// touching the sensor face, see [`DistanceResult::has_face_contact`].
+pub const FACE_CONTACT_MAX_DISTANCE: f32 = 0.05;
+
+/// Compact summary of a [`DistanceResult`], suitable for a single log line.
+#[derive(Debug, Copy, Clone, defmt::Format)]
+pub struct DistanceSummary {
+    /// Number of detected distances
+    pub num_distances: u8,
+    /// Closest detected distance, if any
+    pub closest: Option<Distance>,
+    /// Detected distance with the highest strength, if any
+    pub strongest: Option<Distance>,
+    /// Temperature during the detection
+    pub temperature: i16,
+}
+
+impl Display for DistanceSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} distances", self.num_distances)?;
+        if let Some(closest) = self.closest {
+            write!(f, ", closest {}", closest)?;
+        }
+        if let Some(strongest) = self.strongest {
+            write!(f, ", strongest {}", strongest)?;
+        }
+        write!(f, ", temperature {}", self.temperature)
+    }
+}
+
 /// Encapsulates the results of a distance detection operation.
 ///
 /// This struct contains the distances detected by the radar, along with metadata
@@ -32,9 +88,11 @@ pub struct DistanceResult<'a> {
     radar_config: &'a RadarConfig,
     distances: [Distance; ACC_DETECTOR_DISTANCE_RESULT_MAX_NUM_DISTANCES as usize],
     num_distances: u8,
+    truncated: bool,
     near_start_edge_status: bool,
     calibration_needed: bool,
     temperature: i16,
+    phase_enhancement_disabled: bool,
 }
 
 impl<'a> DistanceResult<'a> {
@@ -49,9 +107,11 @@ impl<'a> DistanceResult<'a> {
             distances: [Distance::default();
                 ACC_DETECTOR_DISTANCE_RESULT_MAX_NUM_DISTANCES as usize],
             num_distances: 0,
+            truncated: false,
             near_start_edge_status: false,
             calibration_needed: false,
             temperature: 0,
+            phase_enhancement_disabled: false,
         }
     }
 
@@ -70,8 +130,16 @@ impl<'a> DistanceResult<'a> {
     }
 
     pub(super) fn update_from_detector_result(&mut self, inner: acc_detector_distance_result_t) {
-        self.num_distances = inner.num_distances;
-        for i in 0..inner.num_distances as usize {
+        let max_num_distances = ACC_DETECTOR_DISTANCE_RESULT_MAX_NUM_DISTANCES as usize;
+        self.truncated = inner.num_distances as usize > max_num_distances;
+        if self.truncated {
+            warn!(
+                "Detector reported {} distances, truncating to {}",
+                inner.num_distances, max_num_distances
+            );
+        }
+        self.num_distances = (inner.num_distances as usize).min(max_num_distances) as u8;
+        for i in 0..self.num_distances as usize {
             self.distances[i].distance = inner.distances[i];
             self.distances[i].strength = inner.strengths[i];
         }
@@ -85,11 +153,81 @@ impl<'a> DistanceResult<'a> {
         &self.distances[0..self.num_distances as usize]
     }
 
+    /// Returns true if the sensor configuration programmed by the detector had phase
+    /// enhancement disabled in a subsweep, degrading the coherent filtering of this result.
+    pub fn phase_enhancement_disabled(&self) -> bool {
+        self.phase_enhancement_disabled
+    }
+
+    pub(super) fn set_phase_enhancement_disabled(&mut self, disabled: bool) {
+        self.phase_enhancement_disabled = disabled;
+    }
+
+    /// Writes the detected distances as an aligned table, one distance per line.
+    #[cfg(feature = "std")]
+    pub fn fmt_table<W: core::fmt::Write>(&self, f: &mut W) -> core::fmt::Result {
+        writeln!(
+            f,
+            "{:>3} | {:>10} | {:>13}",
+            "#", "distance m", "strength dB"
+        )?;
+        for (index, distance) in self.distances().iter().enumerate() {
+            writeln!(
+                f,
+                "{:>3} | {:>10.3} | {:>13.1}",
+                index, distance.distance, distance.strength
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns true if the detector reported more distances than could be stored, in which
+    /// case only the first `ACC_DETECTOR_DISTANCE_RESULT_MAX_NUM_DISTANCES` are kept.
+    pub fn was_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Returns a compact summary of the result.
+    pub fn summary(&self) -> DistanceSummary {
+        let distances = self.distances();
+        DistanceSummary {
+            num_distances: self.num_distances,
+            closest: distances
+                .iter()
+                .copied()
+                .min_by(|a, b| a.distance.total_cmp(&b.distance)),
+            strongest: distances
+                .iter()
+                .copied()
+                .max_by(|a, b| a.strength.total_cmp(&b.strength)),
+            temperature: self.temperature,
+        }
+    }
+
     /// Returns the near start edge status.
     pub fn near_start_edge_status(&self) -> bool {
         self.near_start_edge_status
     }
 
+    /// Returns true if an object is likely touching the sensor face.
+    ///
+    /// The SDK sets the near start edge status when an object is located close to the start of
+    /// the measured range, possibly before it, in the close range leakage region. An object at
+    /// the face is then either not reported as a distance at all, or reported with its strongest
+    /// peak at most [`FACE_CONTACT_MAX_DISTANCE`] away. Without the near start edge status, an
+    /// empty result means that nothing was detected.
+    pub fn has_face_contact(&self) -> bool {
+        if !self.near_start_edge_status {
+            return false;
+        }
+        self.distances()
+            .iter()
+            .max_by(|a, b| a.strength.total_cmp(&b.strength))
+            .map_or(true, |strongest| {
+                strongest.distance <= FACE_CONTACT_MAX_DISTANCE
+            })
+    }
+
     /// Returns whether calibration is needed.
     pub fn calibration_needed(&self) -> bool {
         self.calibration_needed
@@ -114,6 +252,59 @@ impl<'a> DistanceResult<'a> {
     pub fn processing_metadata(&self) -> &ProcessingMetaData {
         &self.metadata
     }
+
+    /// Copies the detection into a result that does not borrow the radar configuration, so it
+    /// can be stored or sent to another task.
+    ///
+    /// The processing result and metadata are not kept.
+    pub fn to_owned(&self) -> OwnedDistanceResult {
+        OwnedDistanceResult {
+            distances: self.distances,
+            num_distances: self.num_distances,
+            truncated: self.truncated,
+            near_start_edge_status: self.near_start_edge_status,
+            calibration_needed: self.calibration_needed,
+            temperature: self.temperature,
+        }
+    }
+}
+
+/// Detection of a [`DistanceResult`], without any borrow, see [`DistanceResult::to_owned`].
+#[derive(Debug, Copy, Clone, defmt::Format)]
+pub struct OwnedDistanceResult {
+    distances: [Distance; ACC_DETECTOR_DISTANCE_RESULT_MAX_NUM_DISTANCES as usize],
+    num_distances: u8,
+    truncated: bool,
+    near_start_edge_status: bool,
+    calibration_needed: bool,
+    temperature: i16,
+}
+
+impl OwnedDistanceResult {
+    /// Returns the detected distances.
+    pub fn distances(&self) -> &[Distance] {
+        &self.distances[0..self.num_distances as usize]
+    }
+
+    /// Returns true if the detector reported more distances than could be stored.
+    pub fn was_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Returns the near start edge status.
+    pub fn near_start_edge_status(&self) -> bool {
+        self.near_start_edge_status
+    }
+
+    /// Returns whether calibration is needed.
+    pub fn calibration_needed(&self) -> bool {
+        self.calibration_needed
+    }
+
+    /// Returns the temperature during the detection.
+    pub fn temperature(&self) -> i16 {
+        self.temperature
+    }
 }
 
 /// Represents the dynamic part of the detector calibration result.
@@ -132,6 +323,148 @@ impl Default for DynamicResult {
     }
 }
 
+impl DynamicResult {
+    /// Size, in bytes, of a serialized dynamic calibration result.
+    pub const SIZE: usize = core::mem::size_of::<acc_detector_cal_result_dynamic_t>();
+
+    /// Returns the raw bytes of the dynamic calibration result, to be persisted alongside the
+    /// static calibration result.
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe {
+            core::slice::from_raw_parts(
+                &self.inner as *const acc_detector_cal_result_dynamic_t as *const u8,
+                Self::SIZE,
+            )
+        }
+    }
+
+    /// Restores a dynamic calibration result from bytes obtained with
+    /// [`as_bytes`](Self::as_bytes).
+    ///
+    /// # Returns
+    /// `Err(SensorError::InvalidBuffer)` if `bytes` is not exactly [`SIZE`](Self::SIZE) bytes
+    /// long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SensorError> {
+        if bytes.len() != Self::SIZE {
+            return Err(SensorError::InvalidBuffer);
+        }
+        let mut result = Self::default();
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                &mut result.inner as *mut acc_detector_cal_result_dynamic_t as *mut u8,
+                Self::SIZE,
+            );
+        }
+        Ok(result)
+    }
+}
+
+/// Distance, in meters, between two consecutive points of a sweep.
+const POINT_LENGTH_M: f32 = 0.0025;
+
+/// Estimates the memory needed by a distance detector from its configuration, without the SDK.
+///
+/// The estimate is the size of a single sweep covering the whole measured interval at the
+/// maximum step length, each point being a complex sample of two `i16`. A profile based step
+/// length is counted as one point, the finest step. The SDK may need more, e.g. to split the
+/// interval in several subsweeps, so [`MemoryReport`] flags any size exceeding the estimate.
+#[derive(Debug, Copy, Clone, PartialEq, defmt::Format)]
+pub struct DistanceMemoryCalculator {
+    start_m: f32,
+    end_m: f32,
+    step_length: u16,
+}
+
+impl DistanceMemoryCalculator {
+    /// Creates a calculator for the interval `start_m..=end_m` measured every `step_length`
+    /// points, 0 meaning profile based.
+    pub fn new(start_m: f32, end_m: f32, step_length: u16) -> Self {
+        Self {
+            start_m,
+            end_m,
+            step_length,
+        }
+    }
+
+    /// Creates a calculator for the interval and maximum step length of `config`.
+    pub fn from_config(config: &RadarDistanceConfig) -> Self {
+        Self::new(
+            config.start_interval(),
+            config.end_interval(),
+            config.max_step_length(),
+        )
+    }
+
+    /// Returns the estimated number of points measured over the interval.
+    pub fn num_points(&self) -> usize {
+        let to_point = |m: f32| (m.max(0.0) / POINT_LENGTH_M + 0.5) as usize;
+        let span = to_point(self.end_m).saturating_sub(to_point(self.start_m));
+        let step = self.step_length.max(1) as usize;
+        span.div_ceil(step) + 1
+    }
+
+    /// Returns the estimated size, in bytes, of the detector buffer.
+    pub fn buffer_size(&self) -> usize {
+        aligned_buffer_size(self.num_points() * 4)
+    }
+}
+
+/// Memory needed by a distance detector, as reported by the SDK for its configuration and
+/// estimated by a [`DistanceMemoryCalculator`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MemoryReport {
+    /// Size, in bytes, of the buffer used for calibration and measurements
+    pub buffer_size: usize,
+    /// Size, in bytes, of the buffer estimated by [`DistanceMemoryCalculator`]
+    pub estimated_buffer_size: usize,
+    /// Size, in bytes, of the static part of the detector calibration result
+    pub static_cal_result_size: usize,
+    /// Size, in bytes, of the scratch buffer owned by the radar
+    pub scratch_size: usize,
+}
+
+impl MemoryReport {
+    /// Returns the total size, in bytes, of the buffers the application must provide.
+    pub fn total(&self) -> usize {
+        self.buffer_size + self.static_cal_result_size
+    }
+
+    /// Returns true if the radar scratch buffer can be used as the detector buffer, see
+    /// [`calibrate_detector_using_scratch`](super::RadarDistanceDetector::calibrate_detector_using_scratch).
+    pub fn scratch_fits(&self) -> bool {
+        self.scratch_size >= self.buffer_size
+    }
+
+    /// Returns true if the SDK demands a larger buffer than estimated.
+    pub fn exceeds_estimate(&self) -> bool {
+        self.buffer_size > self.estimated_buffer_size
+    }
+}
+
+impl defmt::Format for MemoryReport {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "buffer: {} B, static calibration result: {} B, total: {} B, scratch: {} B",
+            self.buffer_size,
+            self.static_cal_result_size,
+            self.total(),
+            self.scratch_size
+        );
+        if !self.scratch_fits() {
+            defmt::write!(f, " (scratch too small for the detector buffer)");
+        }
+        if self.exceeds_estimate() {
+            defmt::write!(
+                f,
+                " (buffer exceeds the estimate of {} B)",
+                self.estimated_buffer_size
+            );
+        }
+    }
+}
+
 /// Stores sizes related to distance detector operations.
 ///
 /// This struct holds information about the required buffer sizes for distance detection
@@ -155,8 +488,128 @@ impl DistanceSizes {
             );
         }
         Self {
-            buffer_size: buffer_size as usize,
-            detector_cal_result_static_size: detector_cal_result_static_size as usize,
+            buffer_size: aligned_buffer_size(buffer_size as usize),
+            detector_cal_result_static_size: aligned_buffer_size(
+                detector_cal_result_static_size as usize,
+            ),
+        }
+    }
+
+    /// Checks that the detector buffer and static calibration result buffer are large enough.
+    ///
+    /// # Returns
+    /// `Err(SensorError::BufferTooSmall)` if either buffer is too small.
+    pub(super) fn check_buffers(
+        &self,
+        buffer_len: usize,
+        static_cal_result_len: usize,
+    ) -> Result<(), SensorError> {
+        if buffer_len < self.buffer_size
+            || static_cal_result_len < self.detector_cal_result_static_size
+        {
+            Err(SensorError::BufferTooSmall)
+        } else {
+            Ok(())
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::radar::SCRATCH_SIZE;
+
+    #[test]
+    fn too_small_scratch_is_rejected() {
+        let sizes = DistanceSizes {
+            buffer_size: SCRATCH_SIZE + 4,
+            detector_cal_result_static_size: 8,
+        };
+        assert!(matches!(
+            sizes.check_buffers(SCRATCH_SIZE, 8),
+            Err(SensorError::BufferTooSmall)
+        ));
+        assert!(matches!(
+            sizes.check_buffers(SCRATCH_SIZE + 4, 4),
+            Err(SensorError::BufferTooSmall)
+        ));
+        assert!(sizes.check_buffers(SCRATCH_SIZE + 4, 8).is_ok());
+    }
+
+    #[test]
+    fn normalized_strengths_are_comparable_across_shapes() {
+        let generic = Distance {
+            distance: 10.0,
+            strength: -15.0,
+        };
+        let planar = Distance {
+            distance: 10.0,
+            strength: 5.0,
+        };
+        assert_eq!(generic.normalized_strength(ReflectorShape::Generic), -15.0);
+        assert_eq!(
+            planar.normalized_strength(ReflectorShape::Planar),
+            generic.normalized_strength(ReflectorShape::Generic)
+        );
+        assert!(
+            planar.normalized_strength(ReflectorShape::Planar)
+                < planar.normalized_strength(ReflectorShape::Generic)
+        );
+    }
+
+    #[test]
+    fn memory_report_compares_sdk_sizes_with_the_estimate() {
+        // 0.5 m to 1.0 m every 4 points of 2.5 mm
+        let calculator = DistanceMemoryCalculator::new(0.5, 1.0, 4);
+        assert_eq!(calculator.num_points(), 51);
+        assert_eq!(calculator.buffer_size(), 204);
+
+        let mut report = MemoryReport {
+            buffer_size: 204,
+            estimated_buffer_size: calculator.buffer_size(),
+            static_cal_result_size: 32,
+            scratch_size: SCRATCH_SIZE,
+        };
+        assert!(!report.exceeds_estimate());
+        assert_eq!(report.total(), 236);
+
+        report.buffer_size = 2 * calculator.buffer_size();
+        assert!(report.exceeds_estimate());
+    }
+
+    #[test]
+    fn profile_based_step_is_estimated_at_the_finest_step() {
+        let profile_based = DistanceMemoryCalculator::new(0.5, 1.0, 0);
+        assert_eq!(profile_based.num_points(), 201);
+        assert_eq!(
+            profile_based.num_points(),
+            DistanceMemoryCalculator::new(0.5, 1.0, 1).num_points()
+        );
+    }
+
+    #[test]
+    fn dynamic_result_round_trips_through_bytes() {
+        let bytes: std::vec::Vec<u8> = (1..=DynamicResult::SIZE as u8).collect();
+        let result = DynamicResult::from_bytes(&bytes).unwrap();
+        assert_eq!(result.as_bytes(), &bytes[..]);
+        let restored = DynamicResult::from_bytes(result.as_bytes()).unwrap();
+        assert_eq!(restored.as_bytes(), result.as_bytes());
+    }
+
+    #[test]
+    fn dynamic_result_rejects_wrong_lengths() {
+        let bytes = [0u8; DynamicResult::SIZE + 1];
+        assert!(matches!(
+            DynamicResult::from_bytes(&bytes[..DynamicResult::SIZE - 1]),
+            Err(SensorError::InvalidBuffer)
+        ));
+        assert!(matches!(
+            DynamicResult::from_bytes(&bytes),
+            Err(SensorError::InvalidBuffer)
+        ));
+        assert!(matches!(
+            DynamicResult::from_bytes(&[]),
+            Err(SensorError::InvalidBuffer)
+        ));
+    }
+}
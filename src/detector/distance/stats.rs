@@ -0,0 +1,169 @@
+use core::time::Duration;
+
+use crate::detector::distance::results::DistanceResult;
+
+/// Monotonic time source used by [`DetectionStats`].
+///
+/// Implement it on top of the timer of the platform, e.g. `embassy_time::Instant`, to keep
+/// this crate independent of any runtime.
+pub trait Clock {
+    /// Returns the time elapsed since an arbitrary, fixed origin.
+    fn now(&self) -> Duration;
+}
+
+/// Rates computed by [`DetectionStats::per_second`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, defmt::Format)]
+pub struct Rates {
+    /// Processed results per second
+    pub frames: f32,
+    /// Results with at least one detected distance per second
+    pub detections: f32,
+    /// Detected distances per second
+    pub distances: f32,
+}
+
+/// Counts the distance results over a time window, to report frame and detection rates.
+///
+/// ```ignore
+/// let mut stats = DetectionStats::new();
+/// loop {
+///     let result = detector.process_data(&mut buffer, &mut static_cal, &mut dynamic_cal)?;
+///     stats.record(&result);
+///     if let Some(rates) = stats.per_second(&clock) {
+///         info!("{}", rates);
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct DetectionStats {
+    window_start: Option<Duration>,
+    frames: u32,
+    detections: u32,
+    distances: u32,
+}
+
+impl DetectionStats {
+    /// Creates an accumulator with no recorded result.
+    pub const fn new() -> Self {
+        Self {
+            window_start: None,
+            frames: 0,
+            detections: 0,
+            distances: 0,
+        }
+    }
+
+    /// Records a processed result.
+    pub fn record(&mut self, result: &DistanceResult) {
+        self.record_distances(result.distances().len() as u32);
+    }
+
+    fn record_distances(&mut self, num_distances: u32) {
+        self.frames = self.frames.saturating_add(1);
+        if num_distances > 0 {
+            self.detections = self.detections.saturating_add(1);
+        }
+        self.distances = self.distances.saturating_add(num_distances);
+    }
+
+    /// Returns the rates over the current window once it lasted at least one second, then
+    /// starts a new window.
+    ///
+    /// The first call only starts the window, discarding the results recorded before it, and
+    /// returns `None`, as does any call made less than a second after the window started.
+    pub fn per_second<C: Clock>(&mut self, clock: &C) -> Option<Rates> {
+        let now = clock.now();
+        let Some(start) = self.window_start else {
+            self.restart(now);
+            return None;
+        };
+        let elapsed = now.checked_sub(start)?;
+        if elapsed < Duration::from_secs(1) {
+            return None;
+        }
+        let seconds = elapsed.as_secs_f32();
+        let rates = Rates {
+            frames: self.frames as f32 / seconds,
+            detections: self.detections as f32 / seconds,
+            distances: self.distances as f32 / seconds,
+        };
+        self.restart(now);
+        Some(rates)
+    }
+
+    /// Returns the number of results recorded in the current window.
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    fn restart(&mut self, now: Duration) {
+        self.window_start = Some(now);
+        self.frames = 0;
+        self.detections = 0;
+        self.distances = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    struct FakeClock(Cell<Duration>);
+
+    impl FakeClock {
+        fn advance(&self, by: Duration) {
+            self.0.set(self.0.get() + by);
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> Duration {
+            self.0.get()
+        }
+    }
+
+    #[test]
+    fn rates_are_reported_once_per_window() {
+        let clock = FakeClock(Cell::new(Duration::from_secs(10)));
+        let mut stats = DetectionStats::new();
+
+        // Results before the first call are discarded
+        stats.record_distances(3);
+        assert_eq!(stats.per_second(&clock), None);
+        assert_eq!(stats.frames(), 0);
+
+        for num_distances in [0, 1, 2, 0] {
+            stats.record_distances(num_distances);
+        }
+        clock.advance(Duration::from_millis(500));
+        assert_eq!(stats.per_second(&clock), None);
+        assert_eq!(stats.frames(), 4);
+
+        clock.advance(Duration::from_millis(1500));
+        assert_eq!(
+            stats.per_second(&clock),
+            Some(Rates {
+                frames: 2.0,
+                detections: 1.0,
+                distances: 1.5,
+            })
+        );
+        assert_eq!(stats.frames(), 0);
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(stats.per_second(&clock), Some(Rates::default()));
+    }
+
+    #[test]
+    fn clock_going_backwards_reports_nothing() {
+        let clock = FakeClock(Cell::new(Duration::from_secs(5)));
+        let mut stats = DetectionStats::new();
+        assert_eq!(stats.per_second(&clock), None);
+
+        stats.record_distances(1);
+        clock.0.set(Duration::from_secs(1));
+        assert_eq!(stats.per_second(&clock), None);
+        assert_eq!(stats.frames(), 1);
+    }
+}
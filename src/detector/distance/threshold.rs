@@ -0,0 +1,158 @@
+//! Threshold method selection based on detection stability.
+//!
+//! CFAR adapts to the environment but can make detections flap on and off when a reflector is
+//! close to the threshold. A recorded threshold is more stable in a static environment, so
+//! [`ThresholdStrategy`] recommends switching to it when detections flap too often. Applying
+//! the recommendation is left to the application.
+
+use crate::detector::distance::config::ThresholdMethod;
+use crate::detector::distance::results::DistanceResult;
+
+/// Maximum number of frames a [`ThresholdStrategy`] can look back on.
+pub const MAX_STABILITY_WINDOW: u8 = 32;
+
+/// Recommends a threshold method from the stability of the detections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub struct ThresholdStrategy {
+    /// Detection history, one bit per frame, the most recent frame in the lowest bit
+    history: u32,
+    len: u8,
+    window: u8,
+    max_flips: u8,
+    recorded_frames: u16,
+}
+
+impl Default for ThresholdStrategy {
+    fn default() -> Self {
+        Self::new(20, 6, 20)
+    }
+}
+
+impl ThresholdStrategy {
+    /// Creates a new strategy.
+    ///
+    /// # Arguments
+    /// * `window` - Number of frames to look back on, at most [`MAX_STABILITY_WINDOW`]
+    /// * `max_flips` - Number of changes between detection and no detection in the window above
+    ///   which detections are considered unstable
+    /// * `recorded_frames` - Number of frames to record the threshold with when recommending
+    ///   [`ThresholdMethod::Recorded`]
+    ///
+    /// # Panics
+    /// Panics if `window` is 0 or greater than [`MAX_STABILITY_WINDOW`].
+    pub fn new(window: u8, max_flips: u8, recorded_frames: u16) -> Self {
+        assert!(
+            (1..=MAX_STABILITY_WINDOW).contains(&window),
+            "Invalid stability window"
+        );
+        Self {
+            history: 0,
+            len: 0,
+            window,
+            max_flips,
+            recorded_frames,
+        }
+    }
+
+    /// Records whether something was detected in the latest frame.
+    pub fn update(&mut self, detected: bool) {
+        self.history = (self.history << 1) | detected as u32;
+        self.len = (self.len + 1).min(self.window);
+    }
+
+    /// Records the latest distance result, counting it as a detection if it has any distance.
+    pub fn update_from_result(&mut self, result: &DistanceResult<'_>) {
+        self.update(result.num_distances() > 0);
+    }
+
+    /// Returns the number of changes between detection and no detection in the window.
+    pub fn flips(&self) -> u8 {
+        if self.len < 2 {
+            return 0;
+        }
+        let mask = u32::MAX >> (32 - (self.len - 1) as u32);
+        ((self.history ^ (self.history >> 1)) & mask).count_ones() as u8
+    }
+
+    /// Returns true if the detections flapped more than allowed in the window.
+    pub fn is_unstable(&self) -> bool {
+        self.flips() > self.max_flips
+    }
+
+    /// Returns the threshold method recommended for the observed detections.
+    ///
+    /// This is [`ThresholdMethod::Recorded`] when detections are unstable, and
+    /// [`ThresholdMethod::Cfar`] otherwise.
+    pub fn recommended_method(&self) -> ThresholdMethod {
+        if self.is_unstable() {
+            ThresholdMethod::Recorded(self.recorded_frames)
+        } else {
+            ThresholdMethod::Cfar
+        }
+    }
+
+    /// Forgets the recorded detections, e.g. after changing the threshold method.
+    pub fn reset(&mut self) {
+        self.history = 0;
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stable_detections_keep_cfar() {
+        let mut strategy = ThresholdStrategy::default();
+        for _ in 0..40 {
+            strategy.update(true);
+        }
+        assert_eq!(strategy.flips(), 0);
+        assert!(matches!(
+            strategy.recommended_method(),
+            ThresholdMethod::Cfar
+        ));
+    }
+
+    #[test]
+    fn flapping_detections_recommend_a_recorded_threshold() {
+        let mut strategy = ThresholdStrategy::new(10, 3, 50);
+        for detected in [true, false, true, false] {
+            strategy.update(detected);
+        }
+        assert_eq!(strategy.flips(), 3);
+        assert!(!strategy.is_unstable());
+
+        strategy.update(true);
+        assert_eq!(strategy.flips(), 4);
+        assert!(matches!(
+            strategy.recommended_method(),
+            ThresholdMethod::Recorded(50)
+        ));
+    }
+
+    #[test]
+    fn flips_leave_the_window() {
+        let mut strategy = ThresholdStrategy::new(4, 1, 50);
+        for detected in [true, false, true, false] {
+            strategy.update(detected);
+        }
+        assert!(strategy.is_unstable());
+        for _ in 0..3 {
+            strategy.update(false);
+        }
+        assert_eq!(strategy.flips(), 0);
+        assert!(!strategy.is_unstable());
+    }
+
+    #[test]
+    fn reset_forgets_the_history() {
+        let mut strategy = ThresholdStrategy::new(4, 0, 50);
+        strategy.update(true);
+        strategy.update(false);
+        assert!(strategy.is_unstable());
+        strategy.reset();
+        assert_eq!(strategy.flips(), 0);
+    }
+}
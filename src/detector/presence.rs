@@ -1,13 +1,20 @@
 pub mod config;
+pub mod noise_floor;
+pub mod owned;
 pub mod results;
+pub mod session;
+pub mod voter;
 
 use crate::detector::presence::config::PresenceConfig;
 use crate::detector::presence::results::{PresenceMetadata, PresenceResult, ProcessDataError};
+use crate::detector::{warn_sensor_mismatch, Detector};
+use crate::hal::aligned_buffer_size;
 use crate::radar::{Radar, Ready};
 use crate::sensor::calibration::CalibrationResult;
 use crate::sensor::error::SensorError;
 use a121_sys::*;
 use core::ffi::c_void;
+use core::time::Duration;
 use embedded_hal::digital::OutputPin;
 use embedded_hal_async::delay::DelayNs;
 use embedded_hal_async::digital::Wait;
@@ -28,6 +35,28 @@ impl InnerPresenceDetector {
         }
     }
 
+    /// Separates the detector from its radar, keeping its handle and state.
+    fn detach(self) -> DetachedPresenceDetector {
+        DetachedPresenceDetector {
+            inner: self.inner,
+            config: self.config,
+            timeout: self.timeout,
+        }
+    }
+
+    /// Attaches a detached detector to `radar`, which must be the radar it was detached from.
+    fn attach(
+        radar: &'radar mut Radar<Ready, SINT, ENABLE, DLY>,
+        detached: DetachedPresenceDetector,
+    ) -> Self {
+        Self {
+            radar,
+            inner: detached.inner,
+            config: detached.config,
+            timeout: detached.timeout,
+        }
+    }
+
     pub fn presence_metadata(&self) -> &PresenceMetadata {
         &self.presence_metadata
     }
@@ -47,6 +76,14 @@ impl Drop for InnerPresenceDetector {
     }
 }
 
+/// State of a presence detector apart from its radar, kept by
+/// [`OwnedPresenceDetector`](owned::OwnedPresenceDetector) between two borrows of the radar.
+struct DetachedPresenceDetector {
+    inner: InnerPresenceDetector,
+    config: PresenceConfig,
+    timeout: Option<Duration>,
+}
+
 pub struct PresenceDetector<'radar, SINT, ENABLE, DLY>
 where
     SINT: Wait,
@@ -56,6 +93,7 @@ where
     pub radar: &'radar mut Radar<Ready, SINT, ENABLE, DLY>,
     inner: InnerPresenceDetector,
     pub config: PresenceConfig,
+    timeout: Option<Duration>,
 }
 
 impl<'radar, SINT, ENABLE, DLY> PresenceDetector<'radar, SINT, ENABLE, DLY>
@@ -65,24 +103,29 @@ where
     DLY: DelayNs,
 {
     pub fn new(radar: &'radar mut Radar<Ready, SINT, ENABLE, DLY>) -> Self {
-        let config = PresenceConfig::default();
+        let mut config = PresenceConfig::default();
+        config.sensor_set(radar.id());
         let inner = InnerPresenceDetector::new(&config);
         Self {
             radar,
             inner,
             config,
+            timeout: None,
         }
     }
 
     pub fn with_config(
         radar: &'radar mut Radar<Ready, SINT, ENABLE, DLY>,
-        config: PresenceConfig,
+        mut config: PresenceConfig,
     ) -> Self {
+        warn_sensor_mismatch(radar.id(), config.sensor_get());
+        config.sensor_set(radar.id());
         let inner = InnerPresenceDetector::new(&config);
         Self {
             radar,
             inner,
             config,
+            timeout: None,
         }
     }
 
@@ -90,11 +133,31 @@ where
         self.inner.presence_metadata()
     }
 
+    /// Sets the maximum time to wait for the sensor interrupt during measurement.
+    /// `None`, the default, waits indefinitely.
+    ///
+    /// When the timeout elapses, the measurement fails with `SensorError::Timeout`.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Returns the maximum time to wait for the sensor interrupt, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Performs a measurement, storing the raw data in `buffer` for
+    /// [`detect_presence`](Self::detect_presence).
+    pub async fn measure(&mut self, buffer: &mut [u8]) -> Result<(), SensorError> {
+        self.radar.measure_inner(buffer, self.timeout).await
+    }
+
     pub async fn prepare_detector(
         &mut self,
         sensor_cal_result: &CalibrationResult,
         buffer: &mut [u8],
     ) -> Result<(), SensorError> {
+        self.radar.check_idle()?;
         let buffer_size = self.get_buffer_size();
 
         if buffer.len() < buffer_size {
@@ -124,7 +187,7 @@ where
         unsafe {
             acc_detector_presence_get_buffer_size(self.inner.inner(), &mut buffer_size as *mut u32);
         }
-        buffer_size as usize
+        aligned_buffer_size(buffer_size as usize)
     }
 
     pub async fn detect_presence(
@@ -132,13 +195,14 @@ where
         buffer: &mut [u8],
     ) -> Result<PresenceResult, ProcessDataError> {
         let mut result = PresenceResult::default();
-        let detection_success = unsafe {
+        let inner = &mut self.inner;
+        let detection_success = result.fill_with(|raw_result| unsafe {
             acc_detector_presence_process(
-                self.inner.inner_mut(),
+                inner.inner_mut(),
                 buffer.as_mut_ptr() as *mut c_void,
-                &mut result.inner() as *mut acc_detector_presence_result_t,
+                raw_result as *mut acc_detector_presence_result_t,
             )
-        };
+        });
 
         if detection_success {
             Ok(result)
@@ -147,3 +211,27 @@ where
         }
     }
 }
+
+impl<'radar, SINT, ENABLE, DLY> Detector for PresenceDetector<'radar, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    /// Buffer prepared with [`prepare_detector`](PresenceDetector::prepare_detector)
+    type Buffers = [u8];
+    type Result<'a>
+        = PresenceResult<'a>
+    where
+        Self: 'a;
+
+    async fn measure_once(
+        &mut self,
+        buffers: &mut Self::Buffers,
+    ) -> Result<Self::Result<'_>, SensorError> {
+        self.measure(buffers).await?;
+        self.detect_presence(buffers)
+            .await
+            .map_err(SensorError::from)
+    }
+}
@@ -60,6 +60,11 @@ impl PresenceConfig {
         unsafe { acc_detector_presence_config_sensor_set(self.inner, sensor_id) }
     }
 
+    /// Returns the sensor ID.
+    pub fn sensor_get(&self) -> u32 {
+        unsafe { acc_detector_presence_config_sensor_get(self.inner) }
+    }
+
     /// Enables or disables automatic profile selection.
     pub fn auto_profile_set(&mut self, enable: bool) {
         unsafe { acc_detector_presence_config_auto_profile_set(self.inner, enable) }
@@ -67,7 +72,7 @@ impl PresenceConfig {
 
     /// Sets the profile for presence detection.
     pub fn profile_set(&mut self, profile: RadarProfile) {
-        unsafe { acc_detector_presence_config_profile_set(self.inner, profile as u32) }
+        unsafe { acc_detector_presence_config_profile_set(self.inner, profile.to_ffi()) }
     }
 
     /// Configures frame rate for presence detection.
@@ -88,6 +93,16 @@ impl PresenceConfig {
         }
     }
 
+    /// Returns the detection threshold for fast movements.
+    pub fn intra_detection_threshold(&self) -> f32 {
+        unsafe { acc_detector_presence_config_intra_detection_threshold_get(self.inner) }
+    }
+
+    /// Returns the detection threshold for slow movements.
+    pub fn inter_detection_threshold(&self) -> f32 {
+        unsafe { acc_detector_presence_config_inter_detection_threshold_get(self.inner) }
+    }
+
     /// Enables or disables intra-frame and inter-frame detection.
     pub fn detection_enable(&mut self, intra_enable: bool, inter_enable: bool) {
         unsafe {
@@ -95,4 +110,14 @@ impl PresenceConfig {
             acc_detector_presence_config_inter_detection_set(self.inner, inter_enable);
         }
     }
+
+    /// Returns true if intra-frame (fast movement) detection is enabled.
+    pub fn is_intra_detection_enabled(&self) -> bool {
+        unsafe { acc_detector_presence_config_intra_detection_get(self.inner) }
+    }
+
+    /// Returns true if inter-frame (slow movement) detection is enabled.
+    pub fn is_inter_detection_enabled(&self) -> bool {
+        unsafe { acc_detector_presence_config_inter_detection_get(self.inner) }
+    }
 }
@@ -0,0 +1,118 @@
+use crate::detector::presence::config::PresenceConfig;
+use crate::detector::presence::results::PresenceResult;
+
+/// Estimates the intra and inter presence scores of an empty scene.
+///
+/// The SDK does not report the noise floor it learns, so this estimates it on the host from
+/// the scores of the frames where no presence is detected, using an exponential moving
+/// average. Comparing the estimate with the detection thresholds shows how much margin the
+/// thresholds leave, which helps tuning them.
+///
+/// ```ignore
+/// let mut noise_floor = NoiseFloor::new(0.05);
+/// let result = detector.detect_presence(&mut buffer).await?;
+/// noise_floor.update(&result);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, defmt::Format)]
+pub struct NoiseFloor {
+    smoothing: f32,
+    intra: Option<f32>,
+    inter: Option<f32>,
+}
+
+impl NoiseFloor {
+    /// Creates an estimator with no recorded frame.
+    ///
+    /// `smoothing` is the weight of the latest frame in the moving average, between 0 and 1:
+    /// higher values follow changes of the scene faster but give a noisier estimate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `smoothing` is not in `(0, 1]`.
+    pub fn new(smoothing: f32) -> Self {
+        assert!(
+            smoothing > 0.0 && smoothing <= 1.0,
+            "smoothing must be in (0, 1]"
+        );
+        Self {
+            smoothing,
+            intra: None,
+            inter: None,
+        }
+    }
+
+    /// Updates the estimate with the scores of a result, if no presence was detected in it.
+    ///
+    /// # Returns
+    /// True if the result was used for the estimate.
+    pub fn update(&mut self, result: &PresenceResult) -> bool {
+        if result.presence_detected {
+            return false;
+        }
+        self.intra = Some(self.average(self.intra, result.intra_presence_score));
+        self.inter = Some(self.average(self.inter, result.inter_presence_score));
+        true
+    }
+
+    /// Returns the estimated intra presence score of an empty scene, if any frame was recorded.
+    pub fn intra(&self) -> Option<f32> {
+        self.intra
+    }
+
+    /// Returns the estimated inter presence score of an empty scene, if any frame was recorded.
+    pub fn inter(&self) -> Option<f32> {
+        self.inter
+    }
+
+    /// Returns the detection thresholds of `config` as multiples of the estimated noise floor.
+    ///
+    /// # Returns
+    /// `(intra, inter)` margins, `None` if no frame was recorded yet.
+    pub fn margins(&self, config: &PresenceConfig) -> Option<(f32, f32)> {
+        Some((
+            config.intra_detection_threshold() / self.intra?,
+            config.inter_detection_threshold() / self.inter?,
+        ))
+    }
+
+    /// Forgets the recorded frames, e.g. after the scene changed.
+    pub fn reset(&mut self) {
+        self.intra = None;
+        self.inter = None;
+    }
+
+    fn average(&self, previous: Option<f32>, score: f32) -> f32 {
+        match previous {
+            Some(previous) => previous + self.smoothing * (score - previous),
+            None => score,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector::presence::results::tests::{detector_result, filled_result};
+    use a121_sys::acc_int16_complex_t;
+
+    #[test]
+    fn averages_processed_scores_without_presence() {
+        let mut frame = acc_int16_complex_t { real: 0, imag: 0 };
+        let mut noise_floor = NoiseFloor::new(0.5);
+        assert!(noise_floor.update(&filled_result(detector_result(&mut frame, false, 1.0, 2.0))));
+        assert_eq!(noise_floor.intra(), Some(1.0));
+        assert_eq!(noise_floor.inter(), Some(2.0));
+
+        assert!(noise_floor.update(&filled_result(detector_result(&mut frame, false, 3.0, 4.0))));
+        assert_eq!(noise_floor.intra(), Some(2.0));
+        assert_eq!(noise_floor.inter(), Some(3.0));
+    }
+
+    #[test]
+    fn ignores_results_with_presence() {
+        let mut frame = acc_int16_complex_t { real: 0, imag: 0 };
+        let mut noise_floor = NoiseFloor::new(0.5);
+        assert!(!noise_floor.update(&filled_result(detector_result(&mut frame, true, 5.0, 5.0))));
+        assert_eq!(noise_floor.intra(), None);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::detector::presence::config::PresenceConfig;
+use crate::detector::presence::{DetachedPresenceDetector, PresenceDetector};
+use crate::detector::DetachedSlot;
+use crate::radar::{Radar, Ready};
+use core::mem::ManuallyDrop;
+use core::ops::{Deref, DerefMut};
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+
+/// A presence detector that owns its radar.
+///
+/// Created with [`Radar::into_presence_detector`], it can be stored without borrowing the
+/// radar. The detector handle and its state are kept while the radar is lent to a
+/// [`PresenceDetector`] by [`detector`](Self::detector). Use [`release`](Self::release) to get
+/// the radar back.
+pub struct OwnedPresenceDetector<SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    detached: DetachedSlot<DetachedPresenceDetector>,
+    radar: Radar<Ready, SINT, ENABLE, DLY>,
+}
+
+impl<SINT, ENABLE, DLY> OwnedPresenceDetector<SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    fn new(mut radar: Radar<Ready, SINT, ENABLE, DLY>, config: PresenceConfig) -> Self {
+        let detached = PresenceDetector::with_config(&mut radar, config).detach();
+        Self {
+            detached: DetachedSlot::new(detached),
+            radar,
+        }
+    }
+
+    /// Returns the detector, borrowing the owned radar.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a previously returned [`PresenceDetectorRef`] was leaked instead of dropped,
+    /// which loses the detector state.
+    pub fn detector(&mut self) -> PresenceDetectorRef<'_, SINT, ENABLE, DLY> {
+        let detached = self.detached.take();
+        PresenceDetectorRef {
+            detector: ManuallyDrop::new(PresenceDetector::attach(&mut self.radar, detached)),
+            detached: &mut self.detached,
+        }
+    }
+
+    /// Destroys the detector and returns the radar it owned.
+    pub fn release(self) -> Radar<Ready, SINT, ENABLE, DLY> {
+        self.radar
+    }
+}
+
+/// A [`PresenceDetector`] borrowing the radar of an [`OwnedPresenceDetector`], returning
+/// the detector state to it when dropped.
+pub struct PresenceDetectorRef<'a, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    detector: ManuallyDrop<PresenceDetector<'a, SINT, ENABLE, DLY>>,
+    detached: &'a mut DetachedSlot<DetachedPresenceDetector>,
+}
+
+impl<'a, SINT, ENABLE, DLY> Deref for PresenceDetectorRef<'a, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    type Target = PresenceDetector<'a, SINT, ENABLE, DLY>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.detector
+    }
+}
+
+impl<SINT, ENABLE, DLY> DerefMut for PresenceDetectorRef<'_, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.detector
+    }
+}
+
+impl<SINT, ENABLE, DLY> Drop for PresenceDetectorRef<'_, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    fn drop(&mut self) {
+        // The detector is not used after being taken
+        let detector = unsafe { ManuallyDrop::take(&mut self.detector) };
+        self.detached.put(detector.detach());
+    }
+}
+
+impl<SINT, ENABLE, DLY> Radar<Ready, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    /// Moves the radar into a presence detector with default configuration.
+    pub fn into_presence_detector(self) -> OwnedPresenceDetector<SINT, ENABLE, DLY> {
+        OwnedPresenceDetector::new(self, PresenceConfig::default())
+    }
+
+    /// Moves the radar into a presence detector with the provided configuration.
+    pub fn into_presence_detector_with_config(
+        self,
+        config: PresenceConfig,
+    ) -> OwnedPresenceDetector<SINT, ENABLE, DLY> {
+        OwnedPresenceDetector::new(self, config)
+    }
+}
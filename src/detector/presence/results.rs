@@ -1,5 +1,7 @@
 use crate::config::profile::RadarProfile;
+use crate::detector::presence::config::PresenceConfig;
 use crate::processing::ProcessingResult;
+use crate::sensor::error::SensorError;
 use a121_sys::{
     acc_config_profile_t_ACC_CONFIG_PROFILE_5, acc_detector_presence_metadata_t,
     acc_detector_presence_result_t,
@@ -46,6 +48,23 @@ impl PresenceResult<'_> {
         self.processing_result = ProcessingResult::from(result.processing_result);
     }
 
+    /// Lets `process` write into a raw detector result initialized from this one, then updates
+    /// this result from it if `process` succeeded.
+    ///
+    /// # Returns
+    /// The value returned by `process`.
+    pub(super) fn fill_with(
+        &mut self,
+        process: impl FnOnce(&mut acc_detector_presence_result_t) -> bool,
+    ) -> bool {
+        let mut inner = self.inner();
+        let success = process(&mut inner);
+        if success {
+            self.update_from_detector_result(&inner);
+        }
+        success
+    }
+
     pub(super) fn inner(&mut self) -> acc_detector_presence_result_t {
         let processing_result = self.processing_result.clone();
         acc_detector_presence_result_t {
@@ -61,6 +80,60 @@ impl PresenceResult<'_> {
             processing_result: processing_result.into(),
         }
     }
+
+    /// Returns the temperature of the sensor during the detection.
+    pub fn temperature(&self) -> i16 {
+        self.processing_result.temperature()
+    }
+
+    /// Returns true if the data was saturated during the detection.
+    pub fn data_saturated(&self) -> bool {
+        self.processing_result.data_saturated()
+    }
+
+    /// Returns true if the frame was delayed.
+    pub fn frame_delayed(&self) -> bool {
+        self.processing_result.frame_delayed()
+    }
+
+    /// Returns true if fast movement was detected, i.e. intra-frame detection is enabled and
+    /// the intra presence score reached its threshold.
+    pub fn intra_detected(&self, config: &PresenceConfig) -> bool {
+        path_detected(
+            config.is_intra_detection_enabled(),
+            self.intra_presence_score,
+            config.intra_detection_threshold(),
+        )
+    }
+
+    /// Returns true if slow movement was detected, i.e. inter-frame detection is enabled and
+    /// the inter presence score reached its threshold.
+    pub fn inter_detected(&self, config: &PresenceConfig) -> bool {
+        path_detected(
+            config.is_inter_detection_enabled(),
+            self.inter_presence_score,
+            config.inter_detection_threshold(),
+        )
+    }
+
+    /// Returns the intra and inter presence scores as multiples of their detection thresholds.
+    ///
+    /// A value of 1.0 means the score is exactly at its threshold. A threshold of zero
+    /// yields a non-finite normalized score.
+    ///
+    /// # Returns
+    /// `(intra, inter)` normalized scores.
+    pub fn normalized_scores(&self, config: &PresenceConfig) -> (f32, f32) {
+        (
+            self.intra_presence_score / config.intra_detection_threshold(),
+            self.inter_presence_score / config.inter_detection_threshold(),
+        )
+    }
+}
+
+/// Returns true if a detection path is enabled and its score reached its threshold.
+fn path_detected(enabled: bool, score: f32, threshold: f32) -> bool {
+    enabled && score >= threshold
 }
 
 impl Default for PresenceResult<'_> {
@@ -84,6 +157,24 @@ pub enum ProcessDataError {
     CalibrationNeeded,
     ProcessingFailed,
     Unavailable,
+    Sensor(SensorError),
+}
+
+impl From<SensorError> for ProcessDataError {
+    fn from(error: SensorError) -> Self {
+        ProcessDataError::Sensor(error)
+    }
+}
+
+impl From<ProcessDataError> for SensorError {
+    fn from(error: ProcessDataError) -> Self {
+        match error {
+            ProcessDataError::CalibrationNeeded => SensorError::CalibrationInvalid,
+            ProcessDataError::ProcessingFailed => SensorError::ProcessingFailed,
+            ProcessDataError::Unavailable => SensorError::ResultNotAvailable,
+            ProcessDataError::Sensor(error) => error,
+        }
+    }
 }
 
 pub struct PresenceMetadata {
@@ -95,6 +186,15 @@ impl PresenceMetadata {
         self.inner.start_m
     }
 
+    /// Returns the distance, in meters, of the last point.
+    ///
+    /// The SDK does not report it, it is computed from the start, step length and number of
+    /// points.
+    pub fn end_m(&self) -> f32 {
+        self.inner.start_m
+            + self.inner.step_length_m * self.inner.num_points.saturating_sub(1) as f32
+    }
+
     pub fn step_length_m(&self) -> f32 {
         self.inner.step_length_m
     }
@@ -130,3 +230,117 @@ impl From<acc_detector_presence_metadata_t> for PresenceMetadata {
         Self { inner: metadata }
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use a121_sys::{acc_int16_complex_t, acc_processing_result_t};
+
+    const INTRA_SCORES: [f32; 3] = [0.5, 1.5, 2.5];
+    const INTER_SCORES: [f32; 3] = [0.25, 0.75, 1.25];
+
+    /// Returns a raw detector result with non-default values, as written by the SDK.
+    pub(crate) fn detector_result(
+        frame: &mut acc_int16_complex_t,
+        presence_detected: bool,
+        intra_presence_score: f32,
+        inter_presence_score: f32,
+    ) -> acc_detector_presence_result_t {
+        acc_detector_presence_result_t {
+            presence_detected,
+            intra_presence_score,
+            inter_presence_score,
+            presence_distance: 1.2,
+            depthwise_intra_presence_scores: INTRA_SCORES.as_ptr() as *mut _,
+            depthwise_inter_presence_scores: INTER_SCORES.as_ptr() as *mut _,
+            depthwise_presence_scores_length: INTRA_SCORES.len() as u32,
+            processing_result: acc_processing_result_t {
+                data_saturated: true,
+                frame_delayed: true,
+                calibration_needed: true,
+                temperature: 42,
+                frame,
+            },
+        }
+    }
+
+    /// Returns a result filled from `raw` the way the detector fills it.
+    pub(crate) fn filled_result(raw: acc_detector_presence_result_t) -> PresenceResult<'static> {
+        let mut result = PresenceResult::default();
+        assert!(result.fill_with(|inner| {
+            *inner = raw;
+            true
+        }));
+        result
+    }
+
+    #[test]
+    fn fill_with_copies_the_written_result_back() {
+        let mut frame = acc_int16_complex_t { real: 3, imag: -4 };
+        let result = filled_result(detector_result(&mut frame, true, 2.0, 3.0));
+        assert!(result.presence_detected);
+        assert_eq!(result.intra_presence_score, 2.0);
+        assert_eq!(result.inter_presence_score, 3.0);
+        assert_eq!(result.presence_distance, 1.2);
+        assert_eq!(result.depthwise_presence_scores_length, 3);
+        assert_eq!(result.depthwise_intra_presence_scores, &INTRA_SCORES);
+        assert_eq!(result.depthwise_inter_presence_scores, &INTER_SCORES);
+    }
+
+    #[test]
+    fn fill_with_keeps_the_result_on_failure() {
+        let mut frame = acc_int16_complex_t { real: 0, imag: 0 };
+        let raw = detector_result(&mut frame, true, 2.0, 3.0);
+        let mut result = PresenceResult::default();
+        assert!(!result.fill_with(|inner| {
+            *inner = raw;
+            false
+        }));
+        assert!(!result.presence_detected);
+        assert_eq!(result.intra_presence_score, 0.0);
+    }
+
+    #[test]
+    fn status_flags_come_from_the_processing_result() {
+        let mut frame = acc_int16_complex_t { real: 0, imag: 0 };
+        let result = filled_result(detector_result(&mut frame, false, 0.0, 0.0));
+        assert_eq!(result.temperature(), 42);
+        assert!(result.data_saturated());
+        assert!(result.frame_delayed());
+    }
+
+    #[test]
+    fn path_detections_follow_the_enable_flags() {
+        let result = PresenceResult {
+            intra_presence_score: 1.5,
+            inter_presence_score: 1.5,
+            ..PresenceResult::default()
+        };
+        let (intra_threshold, inter_threshold) = (1.0, 2.0);
+        for (intra_enabled, inter_enabled) in [(true, true), (false, true), (true, false)] {
+            assert_eq!(
+                path_detected(intra_enabled, result.intra_presence_score, intra_threshold),
+                intra_enabled
+            );
+            assert!(!path_detected(
+                inter_enabled,
+                result.inter_presence_score,
+                inter_threshold
+            ));
+        }
+        assert!(path_detected(true, result.inter_presence_score, 1.5));
+        assert!(!path_detected(false, result.inter_presence_score, 1.5));
+    }
+
+    #[test]
+    fn detector_errors_map_to_sensor_errors() {
+        assert!(matches!(
+            SensorError::from(ProcessDataError::CalibrationNeeded),
+            SensorError::CalibrationInvalid
+        ));
+        assert!(matches!(
+            SensorError::from(ProcessDataError::Sensor(SensorError::Timeout)),
+            SensorError::Timeout
+        ));
+    }
+}
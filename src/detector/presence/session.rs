@@ -0,0 +1,76 @@
+use crate::detector::presence::results::{PresenceResult, ProcessDataError};
+use crate::detector::presence::PresenceDetector;
+use crate::sensor::calibration::CalibrationResult;
+use crate::sensor::error::SensorError;
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+
+/// High-level helper running the prepare, measure and detect steps of presence detection.
+///
+/// The session keeps the sensor calibration result, so a single call to
+/// [`detect_once`](Self::detect_once) is enough to get a presence result.
+pub struct PresenceSession<'radar, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    /// The underlying presence detector.
+    pub detector: PresenceDetector<'radar, SINT, ENABLE, DLY>,
+    calibration: CalibrationResult,
+}
+
+impl<'radar, SINT, ENABLE, DLY> PresenceSession<'radar, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    /// Creates a new session from a presence detector and a sensor calibration result.
+    pub fn new(
+        detector: PresenceDetector<'radar, SINT, ENABLE, DLY>,
+        calibration: CalibrationResult,
+    ) -> Self {
+        Self {
+            detector,
+            calibration,
+        }
+    }
+
+    /// Returns the sensor calibration result used by the session.
+    pub fn calibration(&self) -> &CalibrationResult {
+        &self.calibration
+    }
+
+    /// Returns the size of the buffer needed by [`detect_once`](Self::detect_once).
+    pub fn buffer_size(&self) -> usize {
+        self.detector.get_buffer_size()
+    }
+
+    /// Prepares the detector, performs a measurement and runs presence detection on it.
+    ///
+    /// # Returns
+    /// `Err(ProcessDataError::CalibrationNeeded)` if the sensor reported that it needs to be
+    /// recalibrated, in which case [`recalibrate`](Self::recalibrate) should be called.
+    pub async fn detect_once(
+        &mut self,
+        buffer: &mut [u8],
+    ) -> Result<PresenceResult, ProcessDataError> {
+        self.detector
+            .prepare_detector(&self.calibration, buffer)
+            .await?;
+        self.detector.measure(buffer).await?;
+        let result = self.detector.detect_presence(buffer).await?;
+        if result.processing_result.calibration_needed() {
+            return Err(ProcessDataError::CalibrationNeeded);
+        }
+        Ok(result)
+    }
+
+    /// Recalibrates the sensor and stores the new calibration result in the session.
+    pub async fn recalibrate(&mut self) -> Result<(), SensorError> {
+        self.calibration = self.detector.radar.calibrate().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use crate::detector::presence::results::PresenceResult;
+
+/// Majority vote over the presence detections of the last `N` frames.
+///
+/// Requiring most of the recent frames to detect presence filters out isolated false
+/// positives, at the cost of reacting up to `N / 2` frames later. No allocation is performed.
+///
+/// ```ignore
+/// let mut voter: PresenceVoter<5> = PresenceVoter::new();
+/// let result = detector.detect_presence(&mut buffer).await?;
+/// let present = voter.update(&result);
+/// ```
+pub struct PresenceVoter<const N: usize> {
+    detections: [bool; N],
+    next: usize,
+    len: usize,
+}
+
+impl<const N: usize> Default for PresenceVoter<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> PresenceVoter<N> {
+    /// Creates a voter with no recorded frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    pub const fn new() -> Self {
+        assert!(N > 0, "PresenceVoter needs a window of at least one frame");
+        Self {
+            detections: [false; N],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Records whether presence was detected in the latest frame, forgetting the oldest frame
+    /// once `N` frames are recorded.
+    pub fn push(&mut self, detected: bool) {
+        self.detections[self.next] = detected;
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    /// Records the latest presence result and returns the updated vote.
+    pub fn update(&mut self, result: &PresenceResult<'_>) -> bool {
+        self.push(result.presence_detected);
+        self.vote()
+    }
+
+    /// Returns true if presence was detected in more than half of the recorded frames.
+    pub fn vote(&self) -> bool {
+        self.detected_count() * 2 > self.len
+    }
+
+    /// Returns the number of recorded frames in which presence was detected.
+    pub fn detected_count(&self) -> usize {
+        self.detections[..self.len]
+            .iter()
+            .filter(|&&detected| detected)
+            .count()
+    }
+
+    /// Returns the number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no frame has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Forgets all the recorded frames.
+    pub fn clear(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector::presence::results::tests::{detector_result, filled_result};
+    use a121_sys::acc_int16_complex_t;
+
+    #[test]
+    fn votes_on_processed_results() {
+        let mut frame = acc_int16_complex_t { real: 0, imag: 0 };
+        let present = filled_result(detector_result(&mut frame, true, 2.0, 2.0));
+        let absent = filled_result(detector_result(&mut frame, false, 0.1, 0.1));
+        let mut voter: PresenceVoter<3> = PresenceVoter::new();
+        assert!(voter.update(&present));
+        assert!(!voter.update(&absent));
+        assert!(voter.update(&present));
+        assert_eq!(voter.detected_count(), 2);
+    }
+
+    #[test]
+    fn forgets_the_oldest_frame() {
+        let mut voter: PresenceVoter<3> = PresenceVoter::new();
+        voter.push(true);
+        voter.push(true);
+        voter.push(false);
+        assert!(voter.vote());
+        voter.push(false);
+        assert_eq!(voter.len(), 3);
+        assert!(!voter.vote());
+        voter.clear();
+        assert!(voter.is_empty());
+    }
+}
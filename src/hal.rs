@@ -1,4 +1,4 @@
-use core::cell::RefCell;
+use core::cell::{Cell, RefCell};
 use core::ffi::{c_char, c_void, CStr};
 use defmt::trace;
 
@@ -11,25 +11,204 @@ use a121_sys::{acc_hal_a121_t, acc_hal_optimization_t, acc_rss_hal_register, acc
 pub type RadarSpi = dyn SpiDevice<u8, Error = SpiErrorKind> + Send;
 pub type RefRadarSpi = &'static mut RadarSpi;
 
-/// Global instance of a Mutex, wrapping a RefCell that optionally contains a mutable reference to a `SpiBus`.
+/// Maximum number of sensors whose SPI devices can be registered at the same time.
+pub const MAX_SENSORS: usize = 4;
+
+/// An SPI device registered for a sensor.
 ///
-/// `SPI_INSTANCE` is used to store and provide controlled access to the SPI device required by the radar sensor.
+/// A slot without sensor id is used for any sensor that has no dedicated slot.
+struct SpiSlot {
+    sensor_id: Option<acc_sensor_id_t>,
+    spi: RefRadarSpi,
+}
+
+/// Global instance of a Mutex, wrapping a RefCell that contains the SPI devices registered for each sensor.
+///
+/// `SPI_INSTANCES` is used to store and provide controlled access to the SPI devices required by the radar sensors.
 /// The `Mutex` ensures thread-safe access in environments where multi-threading is possible, while the `RefCell`
-/// allows for mutable access to the SPI device. This setup is crucial for enabling SPI communications in a safe
+/// allows for mutable access to the SPI devices. This setup is crucial for enabling SPI communications in a safe
 /// and controlled manner within the radar sensor's hardware abstraction layer.
 ///
+/// Transfers are routed to the device registered for the sensor id given by the SDK, or to the device
+/// registered without sensor id if there is none.
+///
 /// # Safety
 ///
-/// The access to the `SPI_INSTANCE` is controlled via a mutex to prevent concurrent access issues.
-/// However, care must be taken to ensure that the SPI device is properly initialized before use
-/// and is not accessed after it has been freed or gone out of scope.
-static SPI_INSTANCE: Mutex<CriticalSectionRawMutex, RefCell<Option<RefRadarSpi>>> =
-    Mutex::new(RefCell::new(None));
+/// The access to the `SPI_INSTANCES` is controlled via a mutex to prevent concurrent access issues.
+/// However, care must be taken to ensure that the SPI devices are properly initialized before use
+/// and are not accessed after they have been freed or gone out of scope.
+static SPI_INSTANCES: Mutex<CriticalSectionRawMutex, RefCell<[Option<SpiSlot>; MAX_SENSORS]>> =
+    Mutex::new(RefCell::new([None, None, None, None]));
+
+/// Stores the SPI device used for `sensor_id`, replacing any device previously registered for it.
+///
+/// # Panics
+///
+/// Panics if devices are already registered for [`MAX_SENSORS`] other sensors.
+fn register_spi(sensor_id: Option<acc_sensor_id_t>, spi: RefRadarSpi) {
+    SPI_INSTANCES.lock(|cell| {
+        let mut slots = cell.borrow_mut();
+        let index = slots
+            .iter()
+            .position(|slot| matches!(slot, Some(slot) if slot.sensor_id == sensor_id))
+            .or_else(|| slots.iter().position(Option::is_none))
+            .expect("Too many SPI devices registered");
+        slots[index] = Some(SpiSlot { sensor_id, spi });
+    });
+}
+
+/// Removes the SPI device registered for `sensor_id`, if any.
+fn unregister_spi(sensor_id: Option<acc_sensor_id_t>) {
+    SPI_INSTANCES.lock(|cell| {
+        for slot in cell.borrow_mut().iter_mut() {
+            if matches!(slot, Some(s) if s.sensor_id == sensor_id) {
+                *slot = None;
+            }
+        }
+    });
+}
+
+/// Finds the SPI device to use for `sensor_id`.
+fn find_spi(
+    slots: &mut [Option<SpiSlot>; MAX_SENSORS],
+    sensor_id: acc_sensor_id_t,
+) -> Option<&mut RefRadarSpi> {
+    let index = slots
+        .iter()
+        .position(|slot| matches!(slot, Some(slot) if slot.sensor_id == Some(sensor_id)))
+        .or_else(|| {
+            slots
+                .iter()
+                .position(|slot| matches!(slot, Some(slot) if slot.sensor_id.is_none()))
+        })?;
+    slots[index].as_mut().map(|slot| &mut slot.spi)
+}
+
+/// Global instance of the user-provided logger, if any.
+///
+/// When no logger is registered, log messages from the SDK are forwarded to `defmt`.
+static LOGGER: Mutex<CriticalSectionRawMutex, Cell<Option<&'static dyn RadarLogger>>> =
+    Mutex::new(Cell::new(None));
+
+/// Log levels used by the radar SDK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum LogLevel {
+    /// Something went wrong
+    Error,
+    /// Something unexpected happened, but the SDK can continue
+    Warning,
+    /// Informational message
+    Info,
+    /// Verbose message useful for debugging
+    Verbose,
+    /// Very verbose message useful for debugging
+    Debug,
+}
+
+impl TryFrom<u32> for LogLevel {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(LogLevel::Error),
+            1 => Ok(LogLevel::Warning),
+            2 => Ok(LogLevel::Info),
+            3 => Ok(LogLevel::Verbose),
+            4 => Ok(LogLevel::Debug),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A sink for log messages emitted by the radar SDK.
+///
+/// Implement this trait and register it with [`set_logger`] to route SDK logs somewhere other
+/// than `defmt`.
+pub trait RadarLogger: Sync {
+    /// Logs a message from the SDK.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The severity of the message.
+    /// * `module` - The SDK module emitting the message, empty if unknown.
+    /// * `message` - The message itself.
+    fn log(&self, level: LogLevel, module: &str, message: &str);
+}
+
+/// Registers a logger receiving all log messages from the radar SDK.
+///
+/// This replaces the default `defmt` output and does not require the `nightly-logger` feature.
+pub fn set_logger(logger: &'static dyn RadarLogger) {
+    LOGGER.lock(|cell| cell.set(Some(logger)));
+}
+
+/// Removes the registered logger, restoring the default `defmt` output.
+pub fn clear_logger() {
+    LOGGER.lock(|cell| cell.set(None));
+}
+
+/// Dispatches a log message to the registered logger, or to `defmt` if none is registered.
+fn dispatch_log(level: u32, module: &str, message: &str) {
+    let logger = LOGGER.lock(|cell| cell.get());
+    match (logger, LogLevel::try_from(level)) {
+        (Some(logger), Ok(level)) => logger.log(level, module, message),
+        (None, Ok(LogLevel::Error)) => defmt::error!("{}: {}", module, message),
+        (None, Ok(LogLevel::Warning)) => defmt::warn!("{}: {}", module, message),
+        (None, Ok(LogLevel::Info)) => defmt::info!("{}: {}", module, message),
+        (None, Ok(LogLevel::Verbose)) => defmt::debug!("{}: {}", module, message),
+        (None, Ok(LogLevel::Debug)) => defmt::trace!("{}: {}", module, message),
+        (_, Err(())) => defmt::error!("Unknown log level: {}", level),
+    }
+}
+
+/// Phase of an SPI transfer reported to the SPI trace callback.
+#[cfg(feature = "spi-trace")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum SpiTracePhase {
+    /// The transfer is about to start
+    Start,
+    /// The transfer has completed
+    End,
+}
+
+/// Callback receiving the sensor id, the phase and the length in bytes of each SPI transfer.
+#[cfg(feature = "spi-trace")]
+pub type SpiTraceFn = fn(acc_sensor_id_t, SpiTracePhase, usize);
+
+#[cfg(feature = "spi-trace")]
+static SPI_TRACE: Mutex<CriticalSectionRawMutex, Cell<Option<SpiTraceFn>>> =
+    Mutex::new(Cell::new(None));
+
+/// Installs a callback called before and after every SPI transfer requested by the SDK.
+///
+/// The callback does not receive the transferred data, and is called from the SDK, so it
+/// should return quickly.
+#[cfg(feature = "spi-trace")]
+pub fn set_spi_trace(trace: SpiTraceFn) {
+    SPI_TRACE.lock(|cell| cell.set(Some(trace)));
+}
+
+/// Removes the SPI trace callback, if any.
+#[cfg(feature = "spi-trace")]
+pub fn clear_spi_trace() {
+    SPI_TRACE.lock(|cell| cell.set(None));
+}
+
+#[cfg(feature = "spi-trace")]
+fn trace_spi(sensor_id: acc_sensor_id_t, phase: SpiTracePhase, length: usize) {
+    if let Some(trace) = SPI_TRACE.lock(|cell| cell.get()) {
+        trace(sensor_id, phase, length);
+    }
+}
 
 /// Represents the hardware abstraction layer implementation for the radar sensor.
 ///
 /// This struct encapsulates the necessary functionality to interface with the radar sensor
 /// using the SPI communication protocol and provides methods for memory management and logging.
+///
+/// The SDK does not wait on its own, so the HAL has no delay hook: every wait, for the sensor
+/// interrupt or after toggling the enable pin, is done by [`Radar`](crate::radar::Radar) with its
+/// `DelayNs` implementation.
 pub struct AccHalImpl {
     inner: acc_hal_a121_t,
 }
@@ -49,6 +228,32 @@ impl AccHalImpl {
     where
         SPI: SpiDevice<u8, Error = SpiErrorKind> + Send + 'static,
     {
+        register_spi(None, spi);
+        Self::without_spi()
+    }
+
+    /// Constructs a new `AccHalImpl` instance, registering the SPI device for the given sensor only.
+    ///
+    /// Use this when several sensors share the HAL, each one with its own SPI device.
+    ///
+    /// # Arguments
+    ///
+    /// * `sensor_id` - The id of the sensor the SPI device is connected to.
+    /// * `spi` - A reference to an SPI device that implements the `SpiBus` trait.
+    ///
+    /// # Panics
+    ///
+    /// Panics if SPI devices are already registered for [`MAX_SENSORS`] other sensors.
+    pub fn for_sensor<SPI>(sensor_id: u32, spi: &'static mut SPI) -> Self
+    where
+        SPI: SpiDevice<u8, Error = SpiErrorKind> + Send + 'static,
+    {
+        register_spi(Some(sensor_id as acc_sensor_id_t), spi);
+        Self::without_spi()
+    }
+
+    /// Constructs a new `AccHalImpl` instance without registering any SPI device.
+    fn without_spi() -> Self {
         let inner = acc_hal_a121_t {
             max_spi_transfer_size: u16::MAX,
             mem_alloc: Some(mem_alloc),
@@ -60,10 +265,22 @@ impl AccHalImpl {
             log: Some(a121_sys::c_log_stub),
             optimization: acc_hal_optimization_t { transfer16: None },
         };
-        SPI_INSTANCE.lock(|cell| cell.replace(Some(spi)));
         Self { inner }
     }
 
+    /// Sets the maximum number of bytes transferred in a single SPI transaction.
+    ///
+    /// Must be called before [`register`](Self::register) to take effect. Some SPI
+    /// peripherals or DMA controllers cannot handle transfers as large as the default.
+    pub fn set_max_spi_transfer_size(&mut self, size: u16) {
+        self.inner.max_spi_transfer_size = size;
+    }
+
+    /// Returns the maximum number of bytes transferred in a single SPI transaction.
+    pub fn max_spi_transfer_size(&self) -> u16 {
+        self.inner.max_spi_transfer_size
+    }
+
     /// Transfer function for 16-bit data used by the radar SDK.
     ///
     /// This function is registered as part of the HAL and is called by the radar SDK to
@@ -74,7 +291,7 @@ impl AccHalImpl {
     /// This function is unsafe as it involves raw pointers and direct hardware access.
     #[allow(dead_code)]
     extern "C" fn transfer16_function(
-        _sensor_id: acc_sensor_id_t,
+        sensor_id: acc_sensor_id_t,
         buffer: *mut u16,
         buffer_length: usize,
     ) {
@@ -85,27 +302,31 @@ impl AccHalImpl {
             buffer_length
         );
         // Borrow a mutable reference to the SpiBus
-        SPI_INSTANCE.lock(|cell| unsafe {
+        SPI_INSTANCES.lock(|cell| {
             let mut binding = cell.borrow_mut();
-            let _spi = binding.as_mut().unwrap_unchecked();
+            let _spi = find_spi(&mut binding, sensor_id).expect("No SPI device registered");
             // Perform the SPI transfer
             todo!("Perform the SPI 16 transfer");
         });
     }
 
     extern "C" fn transfer8_function(
-        _sensor_id: acc_sensor_id_t,
+        sensor_id: acc_sensor_id_t,
         buffer: *mut u8,
         buffer_length: usize,
     ) {
         let tmp_buf = unsafe { core::slice::from_raw_parts_mut(buffer, buffer_length) };
-        // Borrow a mutable reference to the SpiBus
-        SPI_INSTANCE.lock(|cell| unsafe {
+        #[cfg(feature = "spi-trace")]
+        trace_spi(sensor_id, SpiTracePhase::Start, buffer_length);
+        // Borrow a mutable reference to the SpiBus registered for this sensor
+        SPI_INSTANCES.lock(|cell| unsafe {
             let mut binding = cell.borrow_mut();
-            let spi = binding.as_mut().unwrap_unchecked();
+            let spi = find_spi(&mut binding, sensor_id).expect("No SPI device registered");
             // Perform the SPI transfer
             spi.transfer_in_place(tmp_buf).unwrap_unchecked();
         });
+        #[cfg(feature = "spi-trace")]
+        trace_spi(sensor_id, SpiTracePhase::End, buffer_length);
     }
 
     /// Registers the HAL implementation with the radar SDK.
@@ -124,18 +345,102 @@ impl AccHalImpl {
     }
 }
 
+/// A set of sensors sharing the HAL, each one with its own SPI device.
+///
+/// Sensors on the same SPI bus are distinguished by their chip select line, which is part of
+/// their `SpiDevice`. SPI transfers requested by the SDK are routed to the device registered
+/// for the sensor id given when creating the sensor.
+pub struct SensorArray {
+    hal: AccHalImpl,
+    sensor_ids: [Option<u32>; MAX_SENSORS],
+}
+
+impl Default for SensorArray {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SensorArray {
+    /// Creates an empty sensor array and registers the HAL with the radar SDK.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HAL registration fails.
+    pub fn new() -> Self {
+        let hal = AccHalImpl::without_spi();
+        hal.register();
+        Self {
+            hal,
+            sensor_ids: [None; MAX_SENSORS],
+        }
+    }
+
+    /// Registers the SPI device of a sensor, replacing any device previously registered for it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if SPI devices are already registered for [`MAX_SENSORS`] other sensors.
+    pub fn add_sensor<SPI>(&mut self, sensor_id: u32, spi: &'static mut SPI)
+    where
+        SPI: SpiDevice<u8, Error = SpiErrorKind> + Send + 'static,
+    {
+        register_spi(Some(sensor_id as acc_sensor_id_t), spi);
+        if !self.sensor_ids.contains(&Some(sensor_id)) {
+            if let Some(slot) = self.sensor_ids.iter_mut().find(|id| id.is_none()) {
+                *slot = Some(sensor_id);
+            }
+        }
+    }
+
+    /// Unregisters the SPI device of a sensor.
+    pub fn remove_sensor(&mut self, sensor_id: u32) {
+        unregister_spi(Some(sensor_id as acc_sensor_id_t));
+        for id in self.sensor_ids.iter_mut() {
+            if *id == Some(sensor_id) {
+                *id = None;
+            }
+        }
+    }
+
+    /// Returns the ids of the registered sensors.
+    pub fn sensor_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.sensor_ids.iter().flatten().copied()
+    }
+
+    /// Returns the HAL shared by the sensors.
+    pub fn hal(&self) -> &AccHalImpl {
+        &self.hal
+    }
+}
+
+/// Alignment, in bytes, expected by the radar SDK for the buffers it is given.
+pub const BUFFER_ALIGNMENT: usize = 4;
+
+/// Rounds `size` up to the next multiple of [`BUFFER_ALIGNMENT`].
+pub const fn aligned_buffer_size(size: usize) -> usize {
+    (size + BUFFER_ALIGNMENT - 1) & !(BUFFER_ALIGNMENT - 1)
+}
+
+#[cfg(not(feature = "no-alloc"))]
 extern "C" {
     fn malloc(size: usize) -> *mut c_void;
     fn free(ptr: *mut c_void);
 }
 
-/// Allocates memory for use by the radar SDK.
+/// Allocates memory for use by the radar SDK, aligned to [`BUFFER_ALIGNMENT`] bytes.
+///
+/// `malloc` returns memory aligned for any fundamental type, which covers
+/// [`BUFFER_ALIGNMENT`]. The size is rounded up with [`aligned_buffer_size`] so that buffers
+/// placed after each other by the SDK stay aligned. `aligned_alloc` is not used, as the libc
+/// shims used by the examples, such as `tinyrlibc`, do not provide it.
 ///
 /// # Safety
 ///
 /// This function is unsafe as it performs raw pointer manipulation.
+#[cfg(not(feature = "no-alloc"))]
 unsafe extern "C" fn mem_alloc(size: usize) -> *mut c_void {
-    malloc(size)
+    malloc(aligned_buffer_size(size))
 }
 
 /// Frees memory previously allocated for the radar SDK.
@@ -143,10 +448,103 @@ unsafe extern "C" fn mem_alloc(size: usize) -> *mut c_void {
 /// # Safety
 ///
 /// This function is unsafe as it performs raw pointer manipulation.
+#[cfg(not(feature = "no-alloc"))]
 unsafe extern "C" fn mem_free(ptr: *mut c_void) {
     free(ptr);
 }
 
+/// Fixed memory region the radar SDK allocates from when the `no-alloc` feature is enabled.
+///
+/// Allocations are bumped from the start of the region, and the whole region is reclaimed once
+/// every allocation has been freed.
+#[cfg(feature = "no-alloc")]
+struct MemoryRegion {
+    start: usize,
+    len: usize,
+    used: usize,
+    live: usize,
+}
+
+#[cfg(feature = "no-alloc")]
+static MEMORY_REGION: Mutex<CriticalSectionRawMutex, RefCell<Option<MemoryRegion>>> =
+    Mutex::new(RefCell::new(None));
+
+/// Registers the memory region the radar SDK allocates from when the `no-alloc` feature is
+/// enabled, replacing any region previously registered.
+///
+/// Must be called before creating any radar, configuration or detector.
+/// The SDK memory is reclaimed only once everything allocated from the region has been
+/// destroyed, so the region must be large enough for all the SDK objects alive at the same time.
+/// Size it from the sizes reported by the SDK for the chosen configuration, such as
+/// [`RadarConfig::config_buffer_size`](crate::config::RadarConfig::config_buffer_size), plus
+/// some margin for the internal sensor, processing and detector handles.
+///
+/// # Panics
+///
+/// Panics if allocations from a previously registered region are still alive.
+#[cfg(feature = "no-alloc")]
+pub fn set_memory_region(region: &'static mut [u8]) {
+    MEMORY_REGION.lock(|cell| {
+        let mut current = cell.borrow_mut();
+        assert!(
+            current.as_ref().map_or(true, |current| current.live == 0),
+            "Memory region still in use"
+        );
+        *current = Some(MemoryRegion {
+            start: region.as_mut_ptr() as usize,
+            len: region.len(),
+            used: 0,
+            live: 0,
+        });
+    });
+}
+
+/// Allocates memory for use by the radar SDK from the registered memory region.
+///
+/// Returns a null pointer if no region is registered or if it is exhausted.
+///
+/// # Safety
+///
+/// This function is unsafe as it performs raw pointer manipulation.
+#[cfg(feature = "no-alloc")]
+unsafe extern "C" fn mem_alloc(size: usize) -> *mut c_void {
+    MEMORY_REGION.lock(|cell| {
+        let mut region = cell.borrow_mut();
+        let Some(region) = region.as_mut() else {
+            return core::ptr::null_mut();
+        };
+        let offset = aligned_buffer_size(region.start + region.used) - region.start;
+        match offset.checked_add(aligned_buffer_size(size)) {
+            Some(end) if end <= region.len => {
+                region.used = end;
+                region.live += 1;
+                (region.start + offset) as *mut c_void
+            }
+            _ => core::ptr::null_mut(),
+        }
+    })
+}
+
+/// Frees memory previously allocated for the radar SDK from the registered memory region.
+///
+/// # Safety
+///
+/// This function is unsafe as it performs raw pointer manipulation.
+#[cfg(feature = "no-alloc")]
+unsafe extern "C" fn mem_free(ptr: *mut c_void) {
+    if ptr.is_null() {
+        return;
+    }
+    MEMORY_REGION.lock(|cell| {
+        if let Some(region) = cell.borrow_mut().as_mut() {
+            region.live = region.live.saturating_sub(1);
+            if region.live == 0 {
+                region.used = 0;
+            }
+        }
+    });
+}
+
 #[cfg(feature = "nightly-logger")]
 unsafe extern "C" fn logger(
     level: a121_sys::acc_log_level_t,
@@ -156,15 +554,11 @@ unsafe extern "C" fn logger(
 ) {
     let module = unsafe { CStr::from_ptr(module) };
     let format = unsafe { CStr::from_ptr(format) };
-    let message = format.to_str().unwrap_or("");
-    match level {
-        0 => defmt::error!("{}: {}", module.to_str().unwrap_or(""), message),
-        1 => defmt::warn!("{}: {}", module.to_str().unwrap_or(""), message),
-        2 => defmt::info!("{}: {}", module.to_str().unwrap_or(""), message),
-        3 => defmt::debug!("{}: {}", module.to_str().unwrap_or(""), message),
-        4 => defmt::trace!("{}: {}", module.to_str().unwrap_or(""), message),
-        _ => defmt::error!("Unknown log level: {}", level),
-    }
+    dispatch_log(
+        level,
+        module.to_str().unwrap_or(""),
+        format.to_str().unwrap_or(""),
+    );
 }
 
 #[cfg(not(feature = "nightly-logger"))]
@@ -174,14 +568,145 @@ unsafe extern "C" fn logger(
 #[no_mangle]
 pub unsafe extern "C" fn rust_log(level: u32, message: *const c_char) {
     let c_str = unsafe { CStr::from_ptr(message) };
-    let str_slice = c_str.to_str().unwrap_or("");
-
-    match level {
-        0 => defmt::error!("{}", str_slice),
-        1 => defmt::warn!("{}", str_slice),
-        2 => defmt::info!("{}", str_slice),
-        3 => defmt::debug!("{}", str_slice),
-        4 => defmt::trace!("{}", str_slice),
-        _ => defmt::error!("Unknown log level: {}", level),
+    dispatch_log(level, "", c_str.to_str().unwrap_or(""));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal::digital::OutputPin;
+    use embedded_hal::spi::{ErrorType, Operation};
+    use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+    use std::boxed::Box;
+    use std::string::{String, ToString};
+    use std::sync::Mutex as StdMutex;
+    use std::vec::Vec;
+
+    /// Logger capturing every message it receives
+    struct Capture(StdMutex<Vec<(LogLevel, String, String)>>);
+
+    impl RadarLogger for Capture {
+        fn log(&self, level: LogLevel, module: &str, message: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((level, module.to_string(), message.to_string()));
+        }
+    }
+
+    #[test]
+    fn registered_logger_captures_sdk_messages() {
+        static CAPTURE: Capture = Capture(StdMutex::new(Vec::new()));
+        set_logger(&CAPTURE);
+        dispatch_log(1, "sensor", "calibration failed");
+        dispatch_log(4, "", "verbose details");
+        clear_logger();
+
+        let captured = CAPTURE.0.lock().unwrap();
+        assert_eq!(
+            *captured,
+            [
+                (
+                    LogLevel::Warning,
+                    "sensor".to_string(),
+                    "calibration failed".to_string()
+                ),
+                (
+                    LogLevel::Debug,
+                    String::new(),
+                    "verbose details".to_string()
+                ),
+            ]
+        );
+    }
+
+    /// SPI device driving its own chip select line, answering every transfer with its id
+    struct CsDevice {
+        id: u8,
+        cs: PinMock,
+    }
+
+    impl ErrorType for CsDevice {
+        type Error = SpiErrorKind;
+    }
+
+    impl SpiDevice<u8> for CsDevice {
+        fn transaction(
+            &mut self,
+            operations: &mut [Operation<'_, u8>],
+        ) -> Result<(), SpiErrorKind> {
+            self.cs.set_low().unwrap();
+            for operation in operations {
+                if let Operation::TransferInPlace(buffer) = operation {
+                    buffer.fill(self.id);
+                }
+            }
+            self.cs.set_high().unwrap();
+            Ok(())
+        }
+    }
+
+    fn cs_line(transfers: usize) -> PinMock {
+        let expectations: Vec<_> = (0..transfers)
+            .flat_map(|_| {
+                [
+                    PinTransaction::set(State::Low),
+                    PinTransaction::set(State::High),
+                ]
+            })
+            .collect();
+        PinMock::new(&expectations)
+    }
+
+    #[test]
+    fn transfers_are_routed_by_sensor_id() {
+        let mut cs1 = cs_line(2);
+        let mut cs2 = cs_line(1);
+        register_spi(
+            Some(1),
+            Box::leak(Box::new(CsDevice {
+                id: 1,
+                cs: cs1.clone(),
+            })),
+        );
+        register_spi(
+            Some(2),
+            Box::leak(Box::new(CsDevice {
+                id: 2,
+                cs: cs2.clone(),
+            })),
+        );
+
+        let mut buffer = [0u8; 3];
+        AccHalImpl::transfer8_function(2, buffer.as_mut_ptr(), buffer.len());
+        assert_eq!(buffer, [2; 3]);
+        AccHalImpl::transfer8_function(1, buffer.as_mut_ptr(), buffer.len());
+        assert_eq!(buffer, [1; 3]);
+        AccHalImpl::transfer8_function(1, buffer.as_mut_ptr(), buffer.len());
+        assert_eq!(buffer, [1; 3]);
+
+        SPI_INSTANCES.lock(|cell| assert!(find_spi(&mut cell.borrow_mut(), 3).is_none()));
+        unregister_spi(Some(1));
+        unregister_spi(Some(2));
+        SPI_INSTANCES.lock(|cell| assert!(find_spi(&mut cell.borrow_mut(), 1).is_none()));
+
+        cs1.done();
+        cs2.done();
+    }
+
+    #[test]
+    fn buffer_sizes_are_aligned_up() {
+        assert_eq!(aligned_buffer_size(0), 0);
+        assert_eq!(aligned_buffer_size(1), 4);
+        assert_eq!(aligned_buffer_size(4), 4);
+        assert_eq!(aligned_buffer_size(5), 8);
+    }
+
+    #[test]
+    fn log_levels_from_sdk() {
+        assert_eq!(LogLevel::try_from(0), Ok(LogLevel::Error));
+        assert_eq!(LogLevel::try_from(2), Ok(LogLevel::Info));
+        assert_eq!(LogLevel::try_from(3), Ok(LogLevel::Verbose));
+        assert_eq!(LogLevel::try_from(5), Err(()));
     }
 }
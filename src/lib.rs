@@ -37,6 +37,7 @@
 //! your projects.
 //!
 
+#[cfg(not(feature = "no-alloc"))]
 extern crate alloc;
 
 /// Configuration for the radar sensor
@@ -51,6 +52,8 @@ pub mod hal;
 pub mod libm;
 /// Number definitions for the radar sensor
 pub mod num;
+/// Power consumption estimates for the radar sensor
+pub mod power;
 /// Processing modules for the radar sensor
 pub mod processing;
 /// Main radar module, interfacing with the radar sensor
@@ -38,6 +38,11 @@ pub extern "C" fn atanf(f: f32) -> f32 {
     libm::atanf(f)
 }
 
+#[no_mangle]
+pub extern "C" fn atan2f(y: f32, x: f32) -> f32 {
+    libm::atan2f(y, x)
+}
+
 #[no_mangle]
 pub extern "C" fn floorf(f: f32) -> f32 {
     libm::floorf(f)
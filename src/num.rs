@@ -1,3 +1,5 @@
+use core::ops::{Add, Sub};
+
 use num::Complex;
 
 use a121_sys::{
@@ -30,6 +32,27 @@ impl AccComplex {
     pub fn ptr(&self) -> *const acc_int16_complex_t {
         &self.inner
     }
+
+    /// Converts the raw sample to a floating point complex number, without scaling.
+    pub fn to_complex32(&self) -> Complex<f32> {
+        Complex::new(self.inner.real as f32, self.inner.imag as f32)
+    }
+
+    /// Converts raw samples to floating point complex numbers, without scaling.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` do not have the same length.
+    pub fn to_complex32_slice(src: &[acc_int16_complex_t], dst: &mut [Complex<f32>]) {
+        assert_eq!(
+            src.len(),
+            dst.len(),
+            "src and dst must have the same length"
+        );
+        for (sample, complex) in src.iter().zip(dst.iter_mut()) {
+            *complex = Complex::new(sample.real as f32, sample.imag as f32);
+        }
+    }
 }
 
 impl Default for AccComplex {
@@ -57,7 +80,7 @@ impl From<Complex<i16>> for AccComplex {
     }
 }
 
-#[derive(Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Points {
     pub points: i32,
 }
@@ -75,4 +98,99 @@ impl Points {
         let points = unsafe { acc_processing_meter_to_points(meters) };
         Self { points }
     }
+
+    /// Returns the distance in meters of each point from `start` (inclusive) to `end`
+    /// (exclusive), `step` points apart.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub fn range_to_meters(start: Points, end: Points, step: u16) -> impl Iterator<Item = f32> {
+        assert!(step > 0, "step must be greater than zero");
+        (start.points..end.points)
+            .step_by(step as usize)
+            .map(|points| Points::new(points).to_meters())
+    }
+}
+
+impl Add for Points {
+    type Output = Points;
+
+    fn add(self, rhs: Points) -> Self::Output {
+        Points::new(self.points + rhs.points)
+    }
+}
+
+impl Sub for Points {
+    type Output = Points;
+
+    fn sub(self, rhs: Points) -> Self::Output {
+        Points::new(self.points - rhs.points)
+    }
+}
+
+impl From<i32> for Points {
+    fn from(points: i32) -> Self {
+        Points::new(points)
+    }
+}
+
+impl From<Points> for i32 {
+    fn from(points: Points) -> Self {
+        points.points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_arithmetic() {
+        assert_eq!(Points::new(10) + Points::new(5), Points::new(15));
+        assert_eq!(Points::new(10) - Points::new(15), Points::new(-5));
+    }
+
+    #[test]
+    fn points_ordering_and_conversions() {
+        assert!(Points::new(-1) < Points::new(0));
+        assert_eq!(Points::new(3).max(Points::new(7)), Points::new(7));
+        assert_eq!(Points::from(42), Points::new(42));
+        assert_eq!(i32::from(Points::new(42)), 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "step must be greater than zero")]
+    fn range_to_meters_rejects_a_zero_step() {
+        let _ = Points::range_to_meters(Points::new(0), Points::new(10), 0);
+    }
+
+    #[test]
+    fn sample_to_complex32() {
+        let sample: AccComplex = Complex::new(-3i16, 4).into();
+        assert_eq!(sample.to_complex32(), Complex::new(-3.0, 4.0));
+    }
+
+    #[test]
+    fn samples_to_complex32_slice() {
+        let src = [
+            acc_int16_complex_t { real: 1, imag: -2 },
+            acc_int16_complex_t {
+                real: i16::MIN,
+                imag: i16::MAX,
+            },
+        ];
+        let mut dst = [Complex::new(0.0, 0.0); 2];
+        AccComplex::to_complex32_slice(&src, &mut dst);
+        assert_eq!(dst[0], Complex::new(1.0, -2.0));
+        assert_eq!(dst[1], Complex::new(-32768.0, 32767.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "src and dst must have the same length")]
+    fn complex32_slice_rejects_a_length_mismatch() {
+        let src = [acc_int16_complex_t { real: 0, imag: 0 }];
+        let mut dst = [Complex::new(0.0, 0.0); 2];
+        AccComplex::to_complex32_slice(&src, &mut dst);
+    }
 }
@@ -0,0 +1,242 @@
+//! Coarse power consumption estimates for the radar sensor.
+//!
+//! The figures used here are approximate typical values for the A121 and are only meant to
+//! compare configurations against each other, e.g. when choosing idle states to meet a power
+//! budget. Measure on the actual hardware for accurate numbers.
+
+use crate::config::frame_rate::FrameRate;
+use crate::config::{RadarConfig, RadarIdleState};
+
+/// Current drawn by the sensor in each of its states, and timing used to estimate how long
+/// the sensor spends measuring.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerProfile {
+    /// Current drawn while measuring, in microamps.
+    pub measure_ua: f32,
+    /// Current drawn in the ready idle state, in microamps.
+    pub ready_ua: f32,
+    /// Current drawn in the sleep idle state, in microamps.
+    pub sleep_ua: f32,
+    /// Current drawn in the deep sleep idle state, in microamps.
+    pub deep_sleep_ua: f32,
+    /// Time needed to measure a single hardware averaged sample, in microseconds.
+    pub sample_duration_us: f32,
+}
+
+impl Default for PowerProfile {
+    /// Approximate typical values for the A121 sensor.
+    fn default() -> Self {
+        Self {
+            measure_ua: 70_000.0,
+            ready_ua: 2_400.0,
+            sleep_ua: 620.0,
+            deep_sleep_ua: 4.0,
+            sample_duration_us: 1.0,
+        }
+    }
+}
+
+/// Timing of the frames measured with a configuration, from which the time spent measuring
+/// and in each idle state is estimated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameTiming {
+    /// Number of hardware averaged samples measured in each sweep, across all subsweeps.
+    pub samples_per_sweep: u32,
+    /// Number of sweeps in each frame.
+    pub sweeps_per_frame: u16,
+    /// Sweep rate in Hz, 0 to sweep as fast as possible.
+    pub sweep_rate: f32,
+    /// Frame rate.
+    pub frame_rate: FrameRate,
+    /// Idle state of the sensor between sweeps.
+    pub inter_sweep_idle_state: RadarIdleState,
+    /// Idle state of the sensor between frames.
+    pub inter_frame_idle_state: RadarIdleState,
+}
+
+impl FrameTiming {
+    /// Returns the timing of the frames measured with the given configuration.
+    pub fn from_config(config: &RadarConfig) -> Self {
+        let samples_per_sweep = config
+            .subsweeps()
+            .map(|subsweep| {
+                u32::from(subsweep.num_points(config))
+                    * u32::from(u16::from(subsweep.hwaas(config)))
+            })
+            .sum();
+        Self {
+            samples_per_sweep,
+            sweeps_per_frame: config.sweeps_per_frame(),
+            sweep_rate: config.sweep_rate(),
+            frame_rate: config.frame_rate(),
+            inter_sweep_idle_state: config.inter_sweep_idle_state(),
+            inter_frame_idle_state: config.inter_frame_idle_state(),
+        }
+    }
+}
+
+impl PowerProfile {
+    /// Returns the current drawn in the given idle state, in microamps.
+    pub fn idle_current_ua(&self, idle_state: &RadarIdleState) -> f32 {
+        match idle_state {
+            RadarIdleState::DeepSleep => self.deep_sleep_ua,
+            RadarIdleState::Sleep => self.sleep_ua,
+            RadarIdleState::Ready => self.ready_ua,
+        }
+    }
+
+    /// Estimates the time needed to measure a frame with the given configuration, in
+    /// microseconds, see [`timing_duration_us`](Self::timing_duration_us).
+    pub fn frame_duration_us(&self, config: &RadarConfig) -> f32 {
+        self.timing_duration_us(&FrameTiming::from_config(config))
+    }
+
+    /// Estimates the time needed to measure a frame with the given timing, in microseconds.
+    ///
+    /// When the sweep rate is limited, this includes the time spent in the inter sweep idle
+    /// state waiting for the next sweep.
+    pub fn timing_duration_us(&self, timing: &FrameTiming) -> f32 {
+        let sweep_us = self.sweep_measure_us(timing);
+        let sweep_period_us = if timing.sweep_rate > 0.0 {
+            (1_000_000.0 / timing.sweep_rate).max(sweep_us)
+        } else {
+            sweep_us
+        };
+        sweep_period_us * f32::from(timing.sweeps_per_frame)
+    }
+
+    /// Estimates the average current drawn with the given configuration, in microamps, see
+    /// [`estimate_timing_current_ua`](Self::estimate_timing_current_ua).
+    pub fn estimate_current_ua(&self, config: &RadarConfig) -> u32 {
+        self.estimate_timing_current_ua(&FrameTiming::from_config(config))
+    }
+
+    /// Estimates the average current drawn with the given timing, in microamps.
+    ///
+    /// The sensor is assumed to measure each sweep, to stay in the inter sweep idle state until
+    /// the next sweep when the sweep rate is limited, and to stay in the inter frame idle state
+    /// for the rest of the frame period. With an unlimited frame rate, frames are assumed to be
+    /// measured back to back.
+    pub fn estimate_timing_current_ua(&self, timing: &FrameTiming) -> u32 {
+        let measure_us = self.sweep_measure_us(timing) * f32::from(timing.sweeps_per_frame);
+        let frame_us = self.timing_duration_us(timing);
+        let period_us = if timing.frame_rate.is_unlimited() {
+            frame_us
+        } else {
+            (1_000_000.0 / timing.frame_rate.value()).max(frame_us)
+        };
+        if period_us <= 0.0 {
+            return self.measure_ua as u32;
+        }
+
+        let sweep_idle_us = frame_us - measure_us;
+        let frame_idle_us = period_us - frame_us;
+        let charge = measure_us * self.measure_ua
+            + sweep_idle_us * self.idle_current_ua(&timing.inter_sweep_idle_state)
+            + frame_idle_us * self.idle_current_ua(&timing.inter_frame_idle_state);
+        (charge / period_us) as u32
+    }
+
+    /// Returns the time spent measuring a single sweep, in microseconds.
+    fn sweep_measure_us(&self, timing: &FrameTiming) -> f32 {
+        timing.samples_per_sweep as f32 * self.sample_duration_us
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(inter_frame_idle_state: RadarIdleState) -> FrameTiming {
+        FrameTiming {
+            samples_per_sweep: 160 * 8,
+            sweeps_per_frame: 16,
+            sweep_rate: 0.0,
+            frame_rate: FrameRate::Limited(10.0),
+            inter_sweep_idle_state: RadarIdleState::Ready,
+            inter_frame_idle_state,
+        }
+    }
+
+    #[test]
+    fn deep_sleep_draws_less_than_ready() {
+        let profile = PowerProfile::default();
+        let deep_sleep = profile.estimate_timing_current_ua(&timing(RadarIdleState::DeepSleep));
+        let sleep = profile.estimate_timing_current_ua(&timing(RadarIdleState::Sleep));
+        let ready = profile.estimate_timing_current_ua(&timing(RadarIdleState::Ready));
+        assert!(deep_sleep < sleep);
+        assert!(sleep < ready);
+    }
+
+    #[test]
+    fn idle_current_per_state() {
+        let profile = PowerProfile::default();
+        assert_eq!(
+            profile.idle_current_ua(&RadarIdleState::DeepSleep),
+            profile.deep_sleep_ua
+        );
+        assert_eq!(
+            profile.idle_current_ua(&RadarIdleState::Sleep),
+            profile.sleep_ua
+        );
+        assert_eq!(
+            profile.idle_current_ua(&RadarIdleState::Ready),
+            profile.ready_ua
+        );
+    }
+
+    #[test]
+    fn averages_measuring_and_idle_time() {
+        let profile = PowerProfile {
+            measure_ua: 1_000.0,
+            ready_ua: 100.0,
+            sleep_ua: 10.0,
+            deep_sleep_ua: 0.0,
+            sample_duration_us: 1.0,
+        };
+        let timing = FrameTiming {
+            samples_per_sweep: 1_000,
+            sweeps_per_frame: 10,
+            sweep_rate: 0.0,
+            frame_rate: FrameRate::Limited(50.0),
+            inter_sweep_idle_state: RadarIdleState::Ready,
+            inter_frame_idle_state: RadarIdleState::DeepSleep,
+        };
+        // Measuring for 10 ms out of a 20 ms period
+        assert_eq!(profile.timing_duration_us(&timing), 10_000.0);
+        assert_eq!(profile.estimate_timing_current_ua(&timing), 500);
+    }
+
+    #[test]
+    fn limited_sweep_rate_idles_between_sweeps() {
+        let profile = PowerProfile {
+            measure_ua: 1_000.0,
+            ready_ua: 100.0,
+            sleep_ua: 10.0,
+            deep_sleep_ua: 0.0,
+            sample_duration_us: 1.0,
+        };
+        let timing = FrameTiming {
+            samples_per_sweep: 1_000,
+            sweeps_per_frame: 10,
+            sweep_rate: 500.0,
+            frame_rate: FrameRate::Limited(25.0),
+            inter_sweep_idle_state: RadarIdleState::Ready,
+            inter_frame_idle_state: RadarIdleState::DeepSleep,
+        };
+        // Each 2 ms sweep period measures for 1 ms, a frame lasts 20 ms out of 40 ms
+        assert_eq!(profile.timing_duration_us(&timing), 20_000.0);
+        assert_eq!(profile.estimate_timing_current_ua(&timing), 275);
+    }
+
+    #[test]
+    fn unlimited_frame_rate_measures_back_to_back() {
+        let profile = PowerProfile::default();
+        let mut timing = timing(RadarIdleState::DeepSleep);
+        timing.frame_rate = FrameRate::Unlimited;
+        assert_eq!(
+            profile.estimate_timing_current_ua(&timing),
+            profile.measure_ua as u32
+        );
+    }
+}
@@ -4,11 +4,13 @@ use metadata::ProcessingMetaData;
 
 use crate::config::RadarConfig;
 use crate::num::AccComplex;
+use crate::sensor::error::SensorError;
 use a121_sys::{
-    acc_processing_create, acc_processing_destroy, acc_processing_execute, acc_processing_result_t,
-    acc_processing_t,
+    acc_int16_complex_t, acc_processing_create, acc_processing_destroy, acc_processing_execute,
+    acc_processing_result_t, acc_processing_t,
 };
 
+pub mod health;
 pub mod metadata;
 
 #[derive(Debug, Clone)]
@@ -18,6 +20,13 @@ pub struct ProcessingResult {
 }
 
 impl ProcessingResult {
+    /// Flag set in [`raw_flags`](Self::raw_flags) when the data was saturated.
+    pub const FLAG_DATA_SATURATED: u32 = 1 << 0;
+    /// Flag set in [`raw_flags`](Self::raw_flags) when the frame was delayed.
+    pub const FLAG_FRAME_DELAYED: u32 = 1 << 1;
+    /// Flag set in [`raw_flags`](Self::raw_flags) when the sensor needs to be recalibrated.
+    pub const FLAG_CALIBRATION_NEEDED: u32 = 1 << 2;
+
     pub fn new() -> Self {
         Self::default()
     }
@@ -32,6 +41,44 @@ impl ProcessingResult {
     pub fn ptr(&self) -> *const acc_processing_result_t {
         &self.inner
     }
+
+    /// Returns true if the data was saturated during the measurement.
+    pub fn data_saturated(&self) -> bool {
+        self.inner.data_saturated
+    }
+
+    /// Returns true if the frame was delayed.
+    pub fn frame_delayed(&self) -> bool {
+        self.inner.frame_delayed
+    }
+
+    /// Returns true if the sensor needs to be recalibrated.
+    pub fn calibration_needed(&self) -> bool {
+        self.inner.calibration_needed
+    }
+
+    /// Returns all status flags reported by the SDK packed in a bit field.
+    ///
+    /// See the `FLAG_*` constants for the meaning of each bit. Bits not listed there are
+    /// reserved and always zero.
+    pub fn raw_flags(&self) -> u32 {
+        let mut flags = 0;
+        if self.data_saturated() {
+            flags |= Self::FLAG_DATA_SATURATED;
+        }
+        if self.frame_delayed() {
+            flags |= Self::FLAG_FRAME_DELAYED;
+        }
+        if self.calibration_needed() {
+            flags |= Self::FLAG_CALIBRATION_NEEDED;
+        }
+        flags
+    }
+
+    /// Returns the temperature of the sensor during the measurement.
+    pub fn temperature(&self) -> i16 {
+        self.inner.temperature
+    }
 }
 
 impl Default for ProcessingResult {
@@ -54,10 +101,27 @@ pub struct Processing {
 }
 
 impl Processing {
+    /// Creates a new processing instance for the given configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the processing instance could not be created, see [`Processing::try_new`].
     pub fn new(config: &RadarConfig) -> Self {
+        Self::try_new(config).expect("Failed to create processing")
+    }
+
+    /// Creates a new processing instance for the given configuration.
+    ///
+    /// # Returns
+    /// `Err(SensorError::InitFailed)` if the SDK failed to create the instance, e.g. because
+    /// memory could not be allocated.
+    pub fn try_new(config: &RadarConfig) -> Result<Self, SensorError> {
         let mut metadata = ProcessingMetaData::new();
         let inner = unsafe { acc_processing_create(config.ptr(), metadata.mut_ptr()) };
-        Self { inner, metadata }
+        if inner.is_null() {
+            return Err(SensorError::InitFailed);
+        }
+        Ok(Self { inner, metadata })
     }
 
     pub fn metadata(&self) -> &ProcessingMetaData {
@@ -75,6 +139,77 @@ impl Processing {
         }
         result
     }
+
+    /// Processes the data measured into `buffer`, returning the result and a view of the
+    /// processed frame, which is stored in `buffer`.
+    pub fn execute_frame<'b>(&mut self, buffer: &'b mut [u8]) -> (ProcessingResult, FrameRef<'b>) {
+        let result = self.execute(buffer);
+        let samples = unsafe {
+            core::slice::from_raw_parts(result.inner.frame, self.metadata.frame_data_length())
+        };
+        (result, FrameRef::new(samples))
+    }
+}
+
+/// View of a processed frame of complex samples.
+#[derive(Debug, Copy, Clone)]
+pub struct FrameRef<'a> {
+    samples: &'a [acc_int16_complex_t],
+}
+
+impl<'a> FrameRef<'a> {
+    /// Creates a view of the given samples.
+    pub fn new(samples: &'a [acc_int16_complex_t]) -> Self {
+        Self { samples }
+    }
+
+    /// Returns the samples of the frame.
+    pub fn samples(&self) -> &'a [acc_int16_complex_t] {
+        self.samples
+    }
+
+    /// Returns the number of complex samples in the frame.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns true if the frame has no sample.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Writes the magnitude of each sample, `sqrt(re² + im²)`, into the first
+    /// [`len`](Self::len) entries of `out`.
+    ///
+    /// # Returns
+    /// `Err(SensorError::BufferTooSmall)` if `out` is shorter than the frame.
+    #[cfg(feature = "libm")]
+    pub fn magnitudes(&self, out: &mut [f32]) -> Result<(), SensorError> {
+        if out.len() < self.len() {
+            return Err(SensorError::BufferTooSmall);
+        }
+        for (magnitude, sample) in out.iter_mut().zip(self.samples) {
+            let (re, im) = (sample.real as f32, sample.imag as f32);
+            *magnitude = ::libm::sqrtf(re * re + im * im);
+        }
+        Ok(())
+    }
+
+    /// Writes the phase of each sample, `atan2(im, re)` in radians, into the first
+    /// [`len`](Self::len) entries of `out`.
+    ///
+    /// # Returns
+    /// `Err(SensorError::BufferTooSmall)` if `out` is shorter than the frame.
+    #[cfg(feature = "libm")]
+    pub fn phases(&self, out: &mut [f32]) -> Result<(), SensorError> {
+        if out.len() < self.len() {
+            return Err(SensorError::BufferTooSmall);
+        }
+        for (phase, sample) in out.iter_mut().zip(self.samples) {
+            *phase = ::libm::atan2f(sample.imag as f32, sample.real as f32);
+        }
+        Ok(())
+    }
 }
 
 impl Drop for Processing {
@@ -100,3 +235,50 @@ impl From<ProcessingResult> for acc_processing_result_t {
         result.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(
+        data_saturated: bool,
+        frame_delayed: bool,
+        calibration_needed: bool,
+    ) -> ProcessingResult {
+        let mut result = ProcessingResult::new();
+        let inner = unsafe { &mut *result.mut_ptr() };
+        inner.data_saturated = data_saturated;
+        inner.frame_delayed = frame_delayed;
+        inner.calibration_needed = calibration_needed;
+        result
+    }
+
+    #[test]
+    fn each_flag_maps_to_its_bit() {
+        let saturated = result(true, false, false);
+        assert!(saturated.data_saturated());
+        assert_eq!(saturated.raw_flags(), ProcessingResult::FLAG_DATA_SATURATED);
+
+        let delayed = result(false, true, false);
+        assert!(delayed.frame_delayed());
+        assert_eq!(delayed.raw_flags(), ProcessingResult::FLAG_FRAME_DELAYED);
+
+        let calibration = result(false, false, true);
+        assert!(calibration.calibration_needed());
+        assert_eq!(
+            calibration.raw_flags(),
+            ProcessingResult::FLAG_CALIBRATION_NEEDED
+        );
+    }
+
+    #[test]
+    fn flags_combine() {
+        assert_eq!(result(false, false, false).raw_flags(), 0);
+        assert_eq!(
+            result(true, true, true).raw_flags(),
+            ProcessingResult::FLAG_DATA_SATURATED
+                | ProcessingResult::FLAG_FRAME_DELAYED
+                | ProcessingResult::FLAG_CALIBRATION_NEEDED
+        );
+    }
+}
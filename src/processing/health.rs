@@ -0,0 +1,162 @@
+use crate::processing::ProcessingResult;
+use crate::sensor::error::SensorError;
+
+/// Escalates adverse processing result flags into errors once they persist.
+///
+/// The flags of a [`ProcessingResult`] are advisory: a saturated frame or a calibration request
+/// does not make the measurement fail. The monitor counts the consecutive results with any of
+/// the monitored flags set, and fails once that count reaches the threshold, for applications
+/// that prefer failing fast over running with degraded data.
+///
+/// # Example
+/// ```ignore
+/// let mut monitor = HealthMonitor::new(5);
+/// let result = monitor.check(radar.measure_processed(&mut buffer).await?)?;
+/// ```
+#[derive(Debug, Clone, defmt::Format)]
+pub struct HealthMonitor {
+    threshold: u16,
+    flags: u32,
+    consecutive: u16,
+}
+
+impl HealthMonitor {
+    /// Creates a monitor failing after `threshold` consecutive saturated or calibration needed
+    /// results.
+    ///
+    /// # Panics
+    /// Panics if `threshold` is 0.
+    pub fn new(threshold: u16) -> Self {
+        Self::with_flags(
+            threshold,
+            ProcessingResult::FLAG_DATA_SATURATED | ProcessingResult::FLAG_CALIBRATION_NEEDED,
+        )
+    }
+
+    /// Creates a monitor failing after `threshold` consecutive results with any of `flags`
+    /// set, see the `FLAG_*` constants of [`ProcessingResult`].
+    ///
+    /// # Panics
+    /// Panics if `threshold` is 0.
+    pub fn with_flags(threshold: u16, flags: u32) -> Self {
+        assert!(threshold > 0, "The threshold must be at least 1");
+        Self {
+            threshold,
+            flags,
+            consecutive: 0,
+        }
+    }
+
+    /// Checks a processing result, returning it if the threshold is not reached.
+    ///
+    /// # Returns
+    /// Once `threshold` consecutive results had a monitored flag set, an error matching the
+    /// flags of this result: `SensorError::PersistentCalibrationNeeded` if calibration is
+    /// needed, `SensorError::PersistentSaturation` if the data was saturated, and
+    /// `SensorError::PersistentFrameDelay` if the frame was delayed.
+    pub fn check(&mut self, result: ProcessingResult) -> Result<ProcessingResult, SensorError> {
+        let adverse = result.raw_flags() & self.flags;
+        if adverse == 0 {
+            self.consecutive = 0;
+            return Ok(result);
+        }
+        self.consecutive = self.consecutive.saturating_add(1);
+        if self.consecutive < self.threshold {
+            return Ok(result);
+        }
+        if adverse & ProcessingResult::FLAG_CALIBRATION_NEEDED != 0 {
+            Err(SensorError::PersistentCalibrationNeeded)
+        } else if adverse & ProcessingResult::FLAG_DATA_SATURATED != 0 {
+            Err(SensorError::PersistentSaturation)
+        } else {
+            Err(SensorError::PersistentFrameDelay)
+        }
+    }
+
+    /// Returns the number of consecutive results with a monitored flag set.
+    pub fn consecutive(&self) -> u16 {
+        self.consecutive
+    }
+
+    /// Returns the number of consecutive adverse results after which the monitor fails.
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// Resets the count of consecutive adverse results, e.g. after recalibrating.
+    pub fn reset(&mut self) {
+        self.consecutive = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(flags: u32) -> ProcessingResult {
+        let mut result = ProcessingResult::new();
+        unsafe {
+            let inner = &mut *result.mut_ptr();
+            inner.data_saturated = flags & ProcessingResult::FLAG_DATA_SATURATED != 0;
+            inner.frame_delayed = flags & ProcessingResult::FLAG_FRAME_DELAYED != 0;
+            inner.calibration_needed = flags & ProcessingResult::FLAG_CALIBRATION_NEEDED != 0;
+        }
+        result
+    }
+
+    #[test]
+    fn fails_once_the_threshold_is_reached() {
+        let mut monitor = HealthMonitor::new(3);
+        let saturated = ProcessingResult::FLAG_DATA_SATURATED;
+        assert!(monitor.check(result(saturated)).is_ok());
+        assert!(monitor.check(result(saturated)).is_ok());
+        assert!(matches!(
+            monitor.check(result(saturated)),
+            Err(SensorError::PersistentSaturation)
+        ));
+        assert_eq!(monitor.consecutive(), 3);
+    }
+
+    #[test]
+    fn a_clean_result_resets_the_count() {
+        let mut monitor = HealthMonitor::new(2);
+        assert!(monitor
+            .check(result(ProcessingResult::FLAG_DATA_SATURATED))
+            .is_ok());
+        assert!(monitor.check(result(0)).is_ok());
+        assert_eq!(monitor.consecutive(), 0);
+        assert!(monitor
+            .check(result(ProcessingResult::FLAG_DATA_SATURATED))
+            .is_ok());
+    }
+
+    #[test]
+    fn unmonitored_flags_are_ignored() {
+        let mut monitor = HealthMonitor::new(1);
+        assert!(monitor
+            .check(result(ProcessingResult::FLAG_FRAME_DELAYED))
+            .is_ok());
+        assert_eq!(monitor.consecutive(), 0);
+    }
+
+    #[test]
+    fn error_matches_the_flags() {
+        let mut monitor = HealthMonitor::with_flags(1, u32::MAX);
+        assert!(matches!(
+            monitor.check(result(ProcessingResult::FLAG_FRAME_DELAYED)),
+            Err(SensorError::PersistentFrameDelay)
+        ));
+        assert!(matches!(
+            monitor.check(result(
+                ProcessingResult::FLAG_CALIBRATION_NEEDED | ProcessingResult::FLAG_DATA_SATURATED
+            )),
+            Err(SensorError::PersistentCalibrationNeeded)
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_threshold_panics() {
+        HealthMonitor::new(0);
+    }
+}
@@ -54,4 +54,9 @@ impl ProcessingMetaData {
     pub fn max_sweep_rate(&self) -> f32 {
         self.inner.max_sweep_rate
     }
+
+    /// Returns true if the sensor runs in high speed mode with the current configuration.
+    pub fn high_speed_mode(&self) -> bool {
+        self.inner.high_speed_mode
+    }
 }
@@ -1,18 +1,145 @@
+#[cfg(not(feature = "no-alloc"))]
+use alloc::vec;
+#[cfg(not(feature = "no-alloc"))]
+use alloc::vec::Vec;
 use core::fmt::{Debug, Display, Formatter};
 use core::marker::PhantomData;
-use embedded_hal::digital::OutputPin;
+use core::ops::{Deref, DerefMut};
+use core::time::Duration;
+use embedded_hal::digital::{InputPin, OutputPin};
 
 use embedded_hal::spi::{ErrorKind as SpiErrorKind, SpiDevice};
 use embedded_hal_async::delay::DelayNs;
 use embedded_hal_async::digital::Wait;
 
-use crate::config::RadarConfig;
-use crate::hal::AccHalImpl;
-use crate::processing::Processing;
+use crate::config::frame_rate::FrameRate;
+use crate::config::profile::RadarProfile;
+use crate::config::{RadarConfig, RadarIdleState, MIN_CALIBRATION_BUFFER_SIZE};
+use crate::hal::{AccHalImpl, BUFFER_ALIGNMENT};
+use crate::processing::{Processing, ProcessingResult};
+use crate::radar::data::RadarData;
 use crate::sensor::calibration::CalibrationResult;
 use crate::sensor::error::SensorError;
 use crate::sensor::Sensor;
-use a121_sys::{acc_sensor_connected, acc_sensor_id_t, acc_sensor_t, acc_version_get_hex};
+use a121_sys::{
+    acc_int16_complex_t, acc_sensor_connected, acc_sensor_id_t, acc_sensor_t, acc_version_get_hex,
+};
+
+/// Container for measured frame data
+pub mod data;
+/// Fixed-capacity recording of measured frames
+pub mod recorder;
+
+/// Size of the scratch buffer owned by the radar, large enough for sensor calibration.
+pub const SCRATCH_SIZE: usize = MIN_CALIBRATION_BUFFER_SIZE;
+
+/// Scratch buffer used by the radar, either allocated by it or provided by the caller.
+///
+/// With the `no-alloc` feature, only a caller-provided buffer is available, keeping the
+/// radar small.
+pub(crate) enum Scratch {
+    #[cfg(not(feature = "no-alloc"))]
+    Owned(Vec<u8>),
+    External(&'static mut [u8]),
+}
+
+impl Scratch {
+    #[cfg(not(feature = "no-alloc"))]
+    fn owned() -> Self {
+        Scratch::Owned(vec![0; SCRATCH_SIZE])
+    }
+
+    /// Uses `scratch` as the scratch buffer.
+    ///
+    /// # Returns
+    /// `Err(SensorError::BufferTooSmall)` if `scratch` is shorter than [`SCRATCH_SIZE`].
+    fn external(scratch: &'static mut [u8]) -> Result<Self, SensorError> {
+        if scratch.len() < SCRATCH_SIZE {
+            return Err(SensorError::BufferTooSmall);
+        }
+        Ok(Scratch::External(scratch))
+    }
+}
+
+impl Deref for Scratch {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            #[cfg(not(feature = "no-alloc"))]
+            Scratch::Owned(scratch) => scratch,
+            Scratch::External(scratch) => scratch,
+        }
+    }
+}
+
+impl DerefMut for Scratch {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            #[cfg(not(feature = "no-alloc"))]
+            Scratch::Owned(scratch) => scratch,
+            Scratch::External(scratch) => scratch,
+        }
+    }
+}
+
+/// Maximum SPI clock frequency supported by the sensor, in Hz.
+pub const MAX_SPI_FREQUENCY: u32 = 50_000_000;
+/// SPI clock frequency known to work with any configuration, in Hz.
+pub const MIN_SPI_FREQUENCY: u32 = 1_000_000;
+
+/// Returns true if `interrupt` is high, a read error being reported as not asserted.
+fn interrupt_asserted<SINT: RadarInterrupt>(interrupt: &mut SINT) -> bool {
+    interrupt.is_high().unwrap_or(false)
+}
+
+/// Checks that a buffer of `len` bytes can hold a frame of `frame_data_length` bytes, see
+/// [`Radar::frame_data_length`].
+///
+/// # Returns
+/// `Err(SensorError::BufferTooSmall)` if `len` is shorter than `frame_data_length`.
+fn check_frame_buffer(len: usize, frame_data_length: usize) -> Result<(), SensorError> {
+    if len < frame_data_length {
+        Err(SensorError::BufferTooSmall)
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that `data` starts at an address aligned to [`BUFFER_ALIGNMENT`] bytes.
+///
+/// # Returns
+/// `Err(SensorError::InvalidBuffer)` if `data` is misaligned.
+fn check_dma_alignment(data: &[u8]) -> Result<(), SensorError> {
+    if data.as_ptr() as usize % BUFFER_ALIGNMENT == 0 {
+        Ok(())
+    } else {
+        Err(SensorError::InvalidBuffer)
+    }
+}
+
+/// Largest temperature change, in degrees Celsius, a sensor calibration is reused for by
+/// [`Radar::calibrate_from`].
+///
+/// Acconeer recommends calibrating the sensor again once its temperature changed by more than
+/// 15 degrees Celsius since it was calibrated.
+pub const CALIBRATION_MAX_TEMPERATURE_DELTA: u16 = 15;
+
+/// Returns true if a calibration made at the `calibrated` temperature can be reused at the
+/// `current` one, i.e. if they are at most `max_delta` degrees Celsius apart.
+fn calibration_reusable(calibrated: i16, current: i16, max_delta: u16) -> bool {
+    (i32::from(calibrated) - i32::from(current)).unsigned_abs() <= u32::from(max_delta)
+}
+
+/// Returns twice the SPI clock frequency, in Hz, needed to read out frames of `frame_bytes`
+/// bytes at `frame_rate`, bounded between [`MIN_SPI_FREQUENCY`] and [`MAX_SPI_FREQUENCY`].
+fn spi_frequency_for(frame_bytes: usize, frame_rate: FrameRate) -> u32 {
+    if frame_rate.is_unlimited() {
+        return MAX_SPI_FREQUENCY;
+    }
+    let bits_per_second = frame_bytes as f32 * 8.0 * frame_rate.value();
+    ((bits_per_second * 2.0) as u32).clamp(MIN_SPI_FREQUENCY, MAX_SPI_FREQUENCY)
+}
 
 pub type TransitionResult<STATEOK, STATERR, SINT, ENABLE, DLY> =
     Result<Radar<STATEOK, SINT, ENABLE, DLY>, TransitionError<STATERR, SINT, ENABLE, DLY>>;
@@ -73,12 +200,173 @@ where
     id: u32,
     pub config: RadarConfig,
     sensor: Sensor<ENABLE, DLY>,
+    /// Processing for the configuration the sensor was last prepared with
     pub processing: Processing,
     pub(crate) interrupt: SINT,
+    pub(crate) scratch: Scratch,
+    drop_policy: DropPolicy,
+    /// Temperature of the last frame processed by [`measure_processed`](Radar::measure_processed)
+    temperature: Option<i16>,
+    /// Calibration to prepare the sensor again with [`config`](Radar::config) before measuring,
+    /// set while a self-test leaves the sensor prepared with another configuration
+    pending_restore: Option<CalibrationResult>,
     _hal: AccHalImpl,
     _state: PhantomData<STATE>,
 }
 
+/// Handling of frames reported as delayed by the processing, see
+/// [`Radar::measure_processed`].
+///
+/// A frame is delayed when it was not read out in time, e.g. because the application could
+/// not keep up with the frame rate.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum DropPolicy {
+    /// Return delayed frames like any other frame
+    #[default]
+    Accept,
+    /// Discard delayed frames, returning `SensorError::FrameDropped`
+    Drop,
+    /// Measure again up to the given number of times, returning the last frame even if it
+    /// was delayed
+    Retry(u8),
+}
+
+/// What [`Radar::measure_processed`] does with a processed frame.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum FrameAction {
+    Return,
+    Drop,
+    Retry,
+}
+
+impl DropPolicy {
+    /// Decides what to do with a frame, given whether it was delayed and how many retries
+    /// are left.
+    fn frame_action(self, delayed: bool, retries_left: u8) -> FrameAction {
+        match self {
+            _ if !delayed => FrameAction::Return,
+            DropPolicy::Accept => FrameAction::Return,
+            DropPolicy::Drop => FrameAction::Drop,
+            DropPolicy::Retry(_) if retries_left == 0 => FrameAction::Return,
+            DropPolicy::Retry(_) => FrameAction::Retry,
+        }
+    }
+}
+
+/// Minimum peak amplitude of the loopback signal for [`Radar::selftest`] to pass.
+///
+/// This is a conservative bound, a working sensor measures a much stronger loopback signal.
+pub const SELFTEST_MIN_AMPLITUDE: u16 = 100;
+
+/// Builds the report of [`Radar::selftest`] from the processed loopback frame.
+fn selftest_report(frame: &[acc_int16_complex_t], saturated: bool) -> SelfTestReport {
+    let peak_amplitude = frame
+        .iter()
+        .map(|sample| sample.real.unsigned_abs().max(sample.imag.unsigned_abs()))
+        .max()
+        .unwrap_or(0);
+    SelfTestReport {
+        passed: !saturated && peak_amplitude >= SELFTEST_MIN_AMPLITUDE,
+        peak_amplitude,
+        saturated,
+    }
+}
+
+/// Outcome of [`Radar::selftest`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub struct SelfTestReport {
+    /// Whether the loopback signal was within the expected bounds
+    pub passed: bool,
+    /// Largest absolute value of the real and imaginary parts of the loopback samples
+    pub peak_amplitude: u16,
+    /// Whether the loopback measurement was saturated
+    pub saturated: bool,
+}
+
+/// Keeps the sensor powered on while it exists, created by [`Radar::enable_guard`] and used by
+/// [`Radar::calibrate`] for its reset.
+///
+/// Powering the sensor off requires waiting, which cannot be done when dropping, so the guard
+/// must be released with [`release`](Self::release). Dropping it without releasing leaves the
+/// sensor powered on and logs a warning.
+pub struct EnableGuard<'a, STATE, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    STATE: RadarState,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    radar: &'a mut Radar<STATE, SINT, ENABLE, DLY>,
+    released: bool,
+}
+
+impl<'a, STATE, SINT, ENABLE, DLY> EnableGuard<'a, STATE, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    STATE: RadarState,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    async fn enable(radar: &'a mut Radar<STATE, SINT, ENABLE, DLY>) -> Self {
+        radar.sensor.enable_sensor().await;
+        Self {
+            radar,
+            released: false,
+        }
+    }
+
+    /// Powers the sensor off, ending the guarded scope.
+    pub async fn release(mut self) {
+        self.radar.sensor.disable_sensor().await;
+        self.released = true;
+    }
+
+    /// Ends the guarded scope, leaving the sensor powered on.
+    fn keep_enabled(mut self) {
+        self.released = true;
+    }
+}
+
+impl<STATE, SINT, ENABLE, DLY> Deref for EnableGuard<'_, STATE, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    STATE: RadarState,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    type Target = Radar<STATE, SINT, ENABLE, DLY>;
+
+    fn deref(&self) -> &Self::Target {
+        self.radar
+    }
+}
+
+impl<STATE, SINT, ENABLE, DLY> DerefMut for EnableGuard<'_, STATE, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    STATE: RadarState,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.radar
+    }
+}
+
+impl<STATE, SINT, ENABLE, DLY> Drop for EnableGuard<'_, STATE, SINT, ENABLE, DLY>
+where
+    SINT: Wait,
+    STATE: RadarState,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    fn drop(&mut self) {
+        if !self.released {
+            defmt::warn!("EnableGuard dropped without release, the sensor is left powered on");
+        }
+    }
+}
+
 /// Radar Sensor Software Version
 /// 0xMMMMmmPP where M is major, m is minor and P is patch
 #[derive(Debug)]
@@ -116,68 +404,247 @@ impl RssVersion {
     }
 }
 
+/// Level of the enable pin that powers the sensor on
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum EnablePolarity {
+    /// The sensor is enabled when the pin is high
+    #[default]
+    ActiveHigh,
+    /// The sensor is enabled when the pin is low, e.g. behind an inverting level shifter
+    ActiveLow,
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Interrupt pin that can be both awaited and read, required by the polling and level checking
+/// features of the radar.
+///
+/// This trait is sealed and implemented for every type implementing both [`Wait`] and
+/// [`InputPin`].
+pub trait RadarInterrupt: Wait + InputPin + sealed::Sealed {}
+
+impl<T: Wait + InputPin> sealed::Sealed for T {}
+impl<T: Wait + InputPin> RadarInterrupt for T {}
+
 impl<SINT, ENABLE, DLY> Radar<Enabled, SINT, ENABLE, DLY>
 where
     SINT: Wait,
     ENABLE: OutputPin,
     DLY: DelayNs,
 {
+    #[cfg(not(feature = "no-alloc"))]
     pub async fn new<SPI>(
+        id: u32,
+        spi: &'static mut SPI,
+        interrupt: SINT,
+        enable_pin: ENABLE,
+        delay: DLY,
+    ) -> Radar<Enabled, SINT, ENABLE, DLY>
+    where
+        SPI: SpiDevice<u8, Error = SpiErrorKind> + Send + 'static,
+    {
+        Self::try_new(id, spi, interrupt, enable_pin, delay)
+            .await
+            .expect("Failed to create radar")
+    }
+
+    /// Creates a new radar instance, returning an error instead of panicking if the sensor
+    /// or processing instances could not be created.
+    ///
+    /// # Returns
+    /// `Err(SensorError::InitFailed)` if the SDK failed to create the sensor or processing
+    /// instance, e.g. because memory could not be allocated.
+    #[cfg(not(feature = "no-alloc"))]
+    pub async fn try_new<SPI>(
+        id: u32,
+        spi: &'static mut SPI,
+        interrupt: SINT,
+        enable_pin: ENABLE,
+        delay: DLY,
+    ) -> Result<Radar<Enabled, SINT, ENABLE, DLY>, SensorError>
+    where
+        SPI: SpiDevice<u8, Error = SpiErrorKind> + Send + 'static,
+    {
+        Self::try_new_with_polarity(
+            id,
+            spi,
+            interrupt,
+            enable_pin,
+            delay,
+            EnablePolarity::ActiveHigh,
+        )
+        .await
+    }
+
+    /// Creates a new radar instance like [`try_new`](Self::try_new), driving the enable pin
+    /// with the given polarity.
+    #[cfg(not(feature = "no-alloc"))]
+    pub async fn try_new_with_polarity<SPI>(
+        id: u32,
+        spi: &'static mut SPI,
+        interrupt: SINT,
+        enable_pin: ENABLE,
+        delay: DLY,
+        polarity: EnablePolarity,
+    ) -> Result<Radar<Enabled, SINT, ENABLE, DLY>, SensorError>
+    where
+        SPI: SpiDevice<u8, Error = SpiErrorKind> + Send + 'static,
+    {
+        Self::try_new_inner(
+            id,
+            spi,
+            interrupt,
+            enable_pin,
+            delay,
+            polarity,
+            Scratch::owned(),
+        )
+        .await
+    }
+
+    /// Creates a new radar instance like [`try_new_with_polarity`](Self::try_new_with_polarity),
+    /// using `scratch` as its scratch buffer instead of allocating one.
+    ///
+    /// This suits platforms where a static buffer is preferable to allocating the scratch
+    /// buffer, and is the only way to create a radar with the `no-alloc` feature.
+    ///
+    /// # Returns
+    /// `Err(SensorError::BufferTooSmall)` if `scratch` is shorter than [`SCRATCH_SIZE`], or
+    /// `Err(SensorError::InitFailed)` if the SDK failed to create the sensor or processing
+    /// instance.
+    pub async fn try_new_with_scratch<SPI>(
+        id: u32,
+        spi: &'static mut SPI,
+        interrupt: SINT,
+        enable_pin: ENABLE,
+        delay: DLY,
+        polarity: EnablePolarity,
+        scratch: &'static mut [u8],
+    ) -> Result<Radar<Enabled, SINT, ENABLE, DLY>, SensorError>
+    where
+        SPI: SpiDevice<u8, Error = SpiErrorKind> + Send + 'static,
+    {
+        let scratch = Scratch::external(scratch)?;
+        Self::try_new_inner(id, spi, interrupt, enable_pin, delay, polarity, scratch).await
+    }
+
+    async fn try_new_inner<SPI>(
         id: u32,
         spi: &'static mut SPI,
         interrupt: SINT,
         mut enable_pin: ENABLE,
         mut delay: DLY,
-    ) -> Radar<Enabled, SINT, ENABLE, DLY>
+        polarity: EnablePolarity,
+        scratch: Scratch,
+    ) -> Result<Radar<Enabled, SINT, ENABLE, DLY>, SensorError>
     where
         SPI: SpiDevice<u8, Error = SpiErrorKind> + Send + 'static,
     {
-        enable_pin.set_high().unwrap();
+        match polarity {
+            EnablePolarity::ActiveHigh => enable_pin.set_high().unwrap(),
+            EnablePolarity::ActiveLow => enable_pin.set_low().unwrap(),
+        }
         delay.delay_ms(2).await;
-        let hal = AccHalImpl::new(spi);
+        let hal = AccHalImpl::for_sensor(id, spi);
         hal.register();
         let config = RadarConfig::default();
-        let sensor = Sensor::new(id, enable_pin, delay).expect("Failed to create sensor");
-        let processing = Processing::new(&config);
-        Self {
+        let sensor = Sensor::new(id, enable_pin, delay, polarity).ok_or(SensorError::InitFailed)?;
+        let processing = Processing::try_new(&config)?;
+        Ok(Self {
             id,
             config,
             interrupt,
             sensor,
             processing,
+            scratch,
+            drop_policy: DropPolicy::default(),
+            temperature: None,
+            pending_restore: None,
             _hal: hal,
             _state: PhantomData,
-        }
+        })
     }
 
+    /// Powers the sensor on for the scope of the returned guard.
+    ///
+    /// The radar is used through the guard, and the sensor is powered off again by
+    /// [`EnableGuard::release`]. Only available before the sensor is prepared, as powering it
+    /// off discards its calibration and preparation.
+    pub async fn enable_guard(&mut self) -> EnableGuard<'_, Enabled, SINT, ENABLE, DLY> {
+        EnableGuard::enable(self).await
+    }
+
+    /// Prepares the sensor with [`config`](Self::config), creating the processing instance
+    /// for it.
     pub fn prepare_sensor(
         mut self,
         calibration_result: &mut CalibrationResult,
     ) -> TransitionResult<Ready, Enabled, SINT, ENABLE, DLY> {
-        let mut buf = [0u8; 2560];
-        if self
+        let processing = match Processing::try_new(&self.config) {
+            Ok(processing) => processing,
+            Err(error) => return Err(TransitionError { radar: self, error }),
+        };
+        match self
             .sensor
-            .prepare(&self.config, calibration_result, &mut buf)
-            .is_ok()
+            .prepare(&self.config, calibration_result, &mut self.scratch)
         {
-            Ok(Radar {
+            Ok(()) => Ok(Radar {
                 id: self.id,
                 config: self.config,
                 sensor: self.sensor,
-                processing: self.processing,
+                processing,
                 interrupt: self.interrupt,
+                scratch: self.scratch,
+                drop_policy: self.drop_policy,
+                temperature: self.temperature,
+                pending_restore: self.pending_restore,
                 _hal: self._hal,
                 _state: PhantomData,
-            })
-        } else {
-            Err(TransitionError {
-                radar: self,
-                error: SensorError::PrepareFailed,
-            })
+            }),
+            Err(error) => Err(TransitionError { radar: self, error }),
         }
     }
 }
 
+impl<SINT, ENABLE, DLY> Radar<Enabled, SINT, ENABLE, DLY>
+where
+    SINT: RadarInterrupt,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    /// Creates a new radar instance like [`try_new`](Self::try_new), with an interrupt pin
+    /// that can also be read, enabling [`is_interrupt_asserted`](Radar::is_interrupt_asserted).
+    #[cfg(not(feature = "no-alloc"))]
+    pub async fn new_polled<SPI>(
+        id: u32,
+        spi: &'static mut SPI,
+        interrupt: SINT,
+        enable_pin: ENABLE,
+        delay: DLY,
+    ) -> Result<Radar<Enabled, SINT, ENABLE, DLY>, SensorError>
+    where
+        SPI: SpiDevice<u8, Error = SpiErrorKind> + Send + 'static,
+    {
+        Self::try_new(id, spi, interrupt, enable_pin, delay).await
+    }
+}
+
+impl<STATE, SINT, ENABLE, DLY> Radar<STATE, SINT, ENABLE, DLY>
+where
+    SINT: RadarInterrupt,
+    ENABLE: OutputPin,
+    DLY: DelayNs,
+{
+    /// Returns true if the sensor interrupt is currently asserted, without waiting for it.
+    ///
+    /// A pin read error is reported as not asserted.
+    pub fn is_interrupt_asserted(&mut self) -> bool {
+        interrupt_asserted(&mut self.interrupt)
+    }
+}
+
 impl<SINT, ENABLE, DLY> Radar<Hibernating, SINT, ENABLE, DLY>
 where
     SINT: Wait,
@@ -192,6 +659,10 @@ where
                 sensor: self.sensor,
                 processing: self.processing,
                 interrupt: self.interrupt,
+                scratch: self.scratch,
+                drop_policy: self.drop_policy,
+                temperature: self.temperature,
+                pending_restore: self.pending_restore,
                 _hal: self._hal,
                 _state: PhantomData,
             })
@@ -210,34 +681,319 @@ where
     ENABLE: OutputPin,
     DLY: DelayNs,
 {
+    /// Tests the RX and TX paths of the sensor by measuring its own transmitted signal through
+    /// loopback, without the need for an external target.
+    ///
+    /// The sensor is calibrated again, the new result being stored in `calibration_result`,
+    /// and prepared with a loopback configuration. Afterwards, the sensor is prepared again
+    /// with [`config`](Self::config), so that measurements can continue as before.
+    ///
+    /// The test passes if the loopback signal is not saturated and its peak amplitude is at
+    /// least [`SELFTEST_MIN_AMPLITUDE`].
+    ///
+    /// If the self-test fails or is cancelled before the sensor is prepared again, e.g. while
+    /// measuring, the sensor is prepared again with [`config`](Self::config) before the next
+    /// measurement, once any pending measurement is drained.
+    pub async fn selftest(
+        &mut self,
+        calibration_result: &mut CalibrationResult,
+    ) -> Result<SelfTestReport, SensorError> {
+        // Calibrating and preparing for loopback both discard the current preparation
+        self.pending_restore = Some(calibration_result.clone());
+        let report = self.selftest_loopback(calibration_result).await;
+        let restored = self.restore_prepared();
+        let report = report?;
+        restored?;
+        Ok(report)
+    }
+
+    async fn selftest_loopback(
+        &mut self,
+        calibration_result: &mut CalibrationResult,
+    ) -> Result<SelfTestReport, SensorError> {
+        let mut loopback = RadarConfig::default();
+        loopback.set_loopback(true);
+        *calibration_result = self.calibrate().await?;
+        self.pending_restore = Some(calibration_result.clone());
+        let mut processing = Processing::try_new(&loopback)?;
+        let frame_length = processing.metadata().frame_data_length();
+
+        self.sensor
+            .prepare(&loopback, calibration_result, &mut self.scratch)?;
+        self.sensor.measure(&mut self.interrupt, None).await?;
+        self.sensor.read(&mut self.scratch)?;
+        let result = processing.execute(&mut self.scratch);
+        // The processed frame is stored in the scratch buffer
+        let frame = unsafe { core::slice::from_raw_parts((*result.ptr()).frame, frame_length) };
+        Ok(selftest_report(frame, result.data_saturated()))
+    }
+
+    /// Prepares the sensor again with [`config`](Self::config) if a self-test left it
+    /// prepared with another configuration.
+    ///
+    /// Nothing is done while a measurement is pending, as the sensor cannot be prepared
+    /// until it is drained.
+    fn restore_prepared(&mut self) -> Result<(), SensorError> {
+        if self.sensor.measurement_in_flight() {
+            return Ok(());
+        }
+        let Some(mut calibration_result) = self.pending_restore.take() else {
+            return Ok(());
+        };
+        let restored = Processing::try_new(&self.config).and_then(|processing| {
+            self.sensor
+                .prepare(&self.config, &mut calibration_result, &mut self.scratch)?;
+            self.processing = processing;
+            Ok(())
+        });
+        if restored.is_err() {
+            self.pending_restore = Some(calibration_result);
+        }
+        restored
+    }
+
+    /// Returns the size in bytes of a measured frame, the minimum length of the buffers given to
+    /// [`measure`](Self::measure) and the other measurement functions.
+    ///
+    /// The size is the one of the configuration the sensor was last prepared with.
+    pub fn frame_data_length(&self) -> usize {
+        self.processing.metadata().frame_data_length() * core::mem::size_of::<acc_int16_complex_t>()
+    }
+
+    /// Returns a recommended SPI clock frequency, in Hz, for the configuration the sensor was
+    /// last prepared with.
+    ///
+    /// The hint is twice the clock needed to read out frames of
+    /// [`frame_data_length`](Self::frame_data_length) bytes at the configured frame rate,
+    /// bounded between [`MIN_SPI_FREQUENCY`] and [`MAX_SPI_FREQUENCY`]. With an unlimited
+    /// frame rate the maximum frequency is recommended. This is advisory only, the actual
+    /// clock is configured by the integrator.
+    pub fn spi_frequency_hint(&self) -> u32 {
+        spi_frequency_for(self.frame_data_length(), self.config.frame_rate())
+    }
+
+    /// Performs a measurement and reads the data into `data`.
+    ///
+    /// This is cancellation safe: if the future is dropped before the measurement completes,
+    /// e.g. when losing a `select`, the pending measurement is discarded by the next one and
+    /// never read as if it were fresh data.
+    ///
+    /// # Returns
+    /// `Err(SensorError::BufferTooSmall)` if `data` is shorter than
+    /// [`frame_data_length`](Self::frame_data_length).
     pub async fn measure<'a>(&mut self, data: &mut [u8]) -> Result<(), SensorError> {
-        if (self.sensor.measure(&mut self.interrupt).await).is_ok() {
-            if self.sensor.read(data).is_ok() {
-                Ok(())
-            } else {
-                Err(SensorError::ReadError)
+        check_frame_buffer(data.len(), self.frame_data_length())?;
+        self.measure_inner(data, None).await
+    }
+
+    /// Performs a measurement into `data` and processes it.
+    ///
+    /// The processed frame is stored in `data`, see
+    /// [`Processing::execute_frame`](crate::processing::Processing::execute_frame) for a
+    /// view of it. Delayed frames are handled according to the
+    /// [`drop_policy`](Radar::drop_policy).
+    ///
+    /// # Returns
+    /// `Err(SensorError::FrameDropped)` if the frame was delayed and the policy is
+    /// [`DropPolicy::Drop`], or `Err(SensorError::BufferTooSmall)` if `data` is shorter than
+    /// [`frame_data_length`](Self::frame_data_length).
+    pub async fn measure_processed(
+        &mut self,
+        data: &mut [u8],
+    ) -> Result<ProcessingResult, SensorError> {
+        check_frame_buffer(data.len(), self.frame_data_length())?;
+        let mut retries = match self.drop_policy {
+            DropPolicy::Retry(retries) => retries,
+            DropPolicy::Accept | DropPolicy::Drop => 0,
+        };
+        loop {
+            self.measure_inner(data, None).await?;
+            let result = self.processing.execute(data);
+            self.temperature = Some(result.temperature());
+            match self
+                .drop_policy
+                .frame_action(result.frame_delayed(), retries)
+            {
+                FrameAction::Return => return Ok(result),
+                FrameAction::Drop => return Err(SensorError::FrameDropped),
+                FrameAction::Retry => retries -= 1,
             }
+        }
+    }
+
+    /// Performs a measurement, giving up if the sensor interrupt is not asserted within
+    /// `timeout`.
+    ///
+    /// # Returns
+    /// `Err(SensorError::Timeout)` if the timeout elapsed before the measurement completed, or
+    /// `Err(SensorError::BufferTooSmall)` if `data` is shorter than
+    /// [`frame_data_length`](Self::frame_data_length).
+    pub async fn measure_with_timeout(
+        &mut self,
+        data: &mut [u8],
+        timeout: Duration,
+    ) -> Result<(), SensorError> {
+        check_frame_buffer(data.len(), self.frame_data_length())?;
+        self.measure_inner(data, Some(timeout)).await
+    }
+
+    /// Performs a measurement, reading the frame into `data`.
+    ///
+    /// # Returns
+    /// The number of valid bytes written to `data`, or `Err(SensorError::BufferTooSmall)` if
+    /// its capacity is smaller than [`frame_data_length`](Self::frame_data_length).
+    pub async fn measure_into<const N: usize>(
+        &mut self,
+        data: &mut RadarData<N>,
+    ) -> Result<usize, SensorError> {
+        let frame_size = self.frame_data_length();
+        check_frame_buffer(data.capacity(), frame_size)?;
+        self.measure(data.storage_mut()).await?;
+        data.set_len(frame_size);
+        Ok(data.len())
+    }
+
+    /// Performs a measurement into a buffer suitable for DMA transfers.
+    ///
+    /// `data` must start at an address aligned to [`BUFFER_ALIGNMENT`] bytes, otherwise the SPI
+    /// driver may not be able to transfer directly into it. Whether `data` lives in memory
+    /// reachable by the DMA cannot be checked here and remains the caller's responsibility.
+    ///
+    /// # Returns
+    /// `Err(SensorError::InvalidBuffer)` if `data` is misaligned, or
+    /// `Err(SensorError::BufferTooSmall)` if it is shorter than
+    /// [`frame_data_length`](Self::frame_data_length).
+    pub async fn measure_dma(&mut self, data: &mut [u8]) -> Result<(), SensorError> {
+        check_dma_alignment(data)?;
+        check_frame_buffer(data.len(), self.frame_data_length())?;
+        self.measure_inner(data, None).await
+    }
+
+    pub(crate) async fn measure_inner(
+        &mut self,
+        data: &mut [u8],
+        timeout: Option<Duration>,
+    ) -> Result<(), SensorError> {
+        self.sensor
+            .drain(&mut self.interrupt, data, timeout)
+            .await?;
+        self.restore_prepared()?;
+        self.sensor.measure(&mut self.interrupt, timeout).await?;
+        self.sensor.read(data)
+    }
+
+    /// Reads out and discards a measurement left pending by a cancelled measurement, using
+    /// `data` as the destination. Does nothing if no measurement is pending.
+    ///
+    /// Measurements drain the sensor themselves, but preparing the sensor, a detector or
+    /// entering hibernation return `Err(SensorError::MeasurementInFlight)` while a measurement
+    /// is pending, so this must be called first.
+    ///
+    /// # Returns
+    /// `Err(SensorError::BufferTooSmall)` if `data` is shorter than
+    /// [`frame_data_length`](Self::frame_data_length).
+    pub async fn drain(&mut self, data: &mut [u8]) -> Result<(), SensorError> {
+        check_frame_buffer(data.len(), self.frame_data_length())?;
+        self.sensor.drain(&mut self.interrupt, data, None).await
+    }
+
+    /// Returns `Err(SensorError::MeasurementInFlight)` if a measurement is pending, see
+    /// [`drain`](Self::drain).
+    pub(crate) fn check_idle(&self) -> Result<(), SensorError> {
+        self.sensor.check_idle()
+    }
+
+    /// Checks that the configured sweep and frame rates can be achieved by the sensor.
+    ///
+    /// The rates are compared against the maximum sweep rate reported in the metadata of a
+    /// processing instance created for the current [`config`](Self::config), so this can be
+    /// checked before preparing the sensor with it. A sweep rate of 0 and an unlimited frame
+    /// rate are always feasible.
+    ///
+    /// # Returns
+    /// `Err(SensorError::TimingInfeasible)` if the configured sweep rate, or the sweep rate
+    /// implied by the frame rate and the number of sweeps per frame, exceeds the maximum, or
+    /// `Err(SensorError::InitFailed)` if the SDK rejected the configuration.
+    pub fn check_timing_feasible(&self) -> Result<(), SensorError> {
+        let processing = Processing::try_new(&self.config)?;
+        let max_sweep_rate = processing.metadata().max_sweep_rate();
+        if max_sweep_rate <= 0.0 {
+            return Ok(());
+        }
+        if self.config.sweep_rate() > max_sweep_rate {
+            return Err(SensorError::TimingInfeasible);
+        }
+        let frame_rate = self.config.frame_rate();
+        if !frame_rate.is_unlimited()
+            && frame_rate.value() * self.config.sweeps_per_frame() as f32 > max_sweep_rate
+        {
+            return Err(SensorError::TimingInfeasible);
+        }
+        Ok(())
+    }
+
+    /// Checks that the configuration allows the sensor to run in high speed mode, warning
+    /// about each setting preventing it.
+    ///
+    /// High speed mode requires continuous sweep mode to be disabled, the inter sweep idle
+    /// state to be `Ready`, a single subsweep and profile 3, 4 or 5. When the configuration
+    /// allows it, a warning is also logged if the metadata of a processing instance created for
+    /// the current [`config`](Self::config) reports that high speed mode is not used.
+    ///
+    /// # Returns
+    /// `Err(SensorError::HighSpeedIncompatible)` if the configuration prevents high speed mode,
+    /// or `Err(SensorError::InitFailed)` if the SDK rejected the configuration.
+    pub fn assert_high_speed_compatible(&self) -> Result<(), SensorError> {
+        let mut compatible = true;
+        if self.config.is_continuous_sweep_mode_enabled() {
+            defmt::warn!("High speed mode requires continuous sweep mode to be disabled");
+            compatible = false;
+        }
+        if self.config.inter_sweep_idle_state() != RadarIdleState::Ready {
+            defmt::warn!("High speed mode requires the inter sweep idle state to be Ready");
+            compatible = false;
+        }
+        if self.config.num_subsweep() > 1 {
+            defmt::warn!("High speed mode requires a single subsweep");
+            compatible = false;
+        }
+        if self.config.profile() < RadarProfile::AccProfile3 {
+            defmt::warn!("High speed mode requires profile 3, 4 or 5");
+            compatible = false;
+        }
+        if compatible
+            && !Processing::try_new(&self.config)?
+                .metadata()
+                .high_speed_mode()
+        {
+            defmt::warn!("Sensor is not in high speed mode although the configuration allows it");
+        }
+        if compatible {
+            Ok(())
         } else {
-            Err(SensorError::MeasurementError)
+            Err(SensorError::HighSpeedIncompatible)
         }
     }
 
     pub fn hibernate_on(mut self) -> TransitionResult<Hibernating, Ready, SINT, ENABLE, DLY> {
-        if self.sensor.hibernate_on().is_ok() {
-            Ok(Radar {
+        if let Err(error) = self.restore_prepared() {
+            return Err(TransitionError { radar: self, error });
+        }
+        match self.sensor.hibernate_on() {
+            Ok(()) => Ok(Radar {
                 id: self.id,
                 config: self.config,
                 sensor: self.sensor,
                 processing: self.processing,
                 interrupt: self.interrupt,
+                scratch: self.scratch,
+                drop_policy: self.drop_policy,
+                temperature: self.temperature,
+                pending_restore: self.pending_restore,
                 _hal: self._hal,
                 _state: PhantomData,
-            })
-        } else {
-            Err(TransitionError {
-                radar: self,
-                error: SensorError::HibernationOnFailed,
-            })
+            }),
+            Err(error) => Err(TransitionError { radar: self, error }),
         }
     }
 }
@@ -253,15 +1009,144 @@ where
         self.id
     }
 
+    /// Resets the sensor, then calibrates it.
+    ///
+    /// The sensor is powered on again for the scope of an [`EnableGuard`], and stays powered on
+    /// only if the calibration succeeds. After a failure it is left powered off, and the next
+    /// calibration powers it on again.
     pub async fn calibrate(&mut self) -> Result<CalibrationResult, SensorError> {
-        let mut buf = [0u8; 5560];
-        self.sensor.calibrate(&mut self.interrupt, &mut buf).await
+        self.calibrate_after_reset(None).await
+    }
+
+    async fn calibrate_after_reset(
+        &mut self,
+        timeout: Option<Duration>,
+    ) -> Result<CalibrationResult, SensorError> {
+        self.sensor.disable_sensor().await;
+        let mut guard = EnableGuard::enable(self).await;
+        let radar = &mut *guard.radar;
+        let result = radar
+            .sensor
+            .calibrate_no_reset(&mut radar.interrupt, &mut radar.scratch, timeout)
+            .await;
+        if result.is_ok() {
+            guard.keep_enabled();
+        } else {
+            guard.release().await;
+        }
+        result
+    }
+
+    /// Calibrates the sensor, skipping the reset done by [`calibrate`](Self::calibrate) if this
+    /// is the first calibration since the sensor was powered on.
+    ///
+    /// Later calibrations reset the sensor as usual.
+    pub async fn calibrate_no_reset(&mut self) -> Result<CalibrationResult, SensorError> {
+        self.sensor
+            .calibrate_no_reset(&mut self.interrupt, &mut self.scratch, None)
+            .await
+    }
+
+    /// Calibrates the sensor, reusing `prior` if it is still valid at the current temperature.
+    ///
+    /// The SDK cannot seed a calibration with a previous result, so the only speedup comes
+    /// from skipping the calibration entirely: `prior` is returned if it passes
+    /// [`CalibrationResult::validate_calibration`] and was made at most
+    /// [`CALIBRATION_MAX_TEMPERATURE_DELTA`] degrees Celsius away from the temperature of the
+    /// last frame processed by [`measure_processed`](Radar::measure_processed). Otherwise, or if
+    /// no frame was processed yet, a full calibration is performed.
+    pub async fn calibrate_from(
+        &mut self,
+        prior: &CalibrationResult,
+    ) -> Result<CalibrationResult, SensorError> {
+        let close_enough = self.temperature.is_some_and(|current| {
+            calibration_reusable(
+                prior.cached_temperature(),
+                current,
+                CALIBRATION_MAX_TEMPERATURE_DELTA,
+            )
+        });
+        if close_enough && prior.validate_calibration().is_ok() {
+            return Ok(prior.clone());
+        }
+        self.calibrate().await
+    }
+
+    /// Calibrates the sensor and reads the temperature the calibration was done at.
+    ///
+    /// # Returns
+    /// The calibration result and its temperature, as given by
+    /// [`CalibrationResult::temperature`].
+    pub async fn calibrate_with_info(&mut self) -> Result<(CalibrationResult, i16), SensorError> {
+        let calibration = self.calibrate().await?;
+        let temperature = calibration.temperature()?;
+        Ok((calibration, temperature))
+    }
+
+    /// Calibrates the sensor, giving up if the sensor interrupt is not asserted within
+    /// `timeout` during any calibration step.
+    ///
+    /// # Returns
+    /// `Err(SensorError::Timeout)` if the timeout elapsed before the calibration completed.
+    pub async fn calibrate_with_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<CalibrationResult, SensorError> {
+        self.calibrate_after_reset(Some(timeout)).await
+    }
+
+    /// Sets a debounce time for the sensor interrupt, see [`interrupt_debounce`](Self::interrupt_debounce).
+    pub fn with_interrupt_debounce(mut self, debounce: Duration) -> Self {
+        self.sensor.set_debounce(Some(debounce));
+        self
+    }
+
+    /// Sets how [`measure_processed`](Radar::measure_processed) handles delayed frames.
+    pub fn set_drop_policy(&mut self, drop_policy: DropPolicy) {
+        self.drop_policy = drop_policy;
+    }
+
+    /// Returns how delayed frames are handled, [`DropPolicy::Accept`] by default.
+    pub fn drop_policy(&self) -> DropPolicy {
+        self.drop_policy
+    }
+
+    /// Returns the interrupt debounce time, if any.
+    ///
+    /// When set, the interrupt is checked again after this time once asserted, so that a
+    /// glitch on a noisy interrupt line is not mistaken for the sensor being ready.
+    pub fn interrupt_debounce(&self) -> Option<Duration> {
+        self.sensor.debounce()
+    }
+
+    /// Waits for the sensor interrupt, giving up after `timeout` if one is given.
+    /// Applies the interrupt debounce, if any.
+    ///
+    /// # Returns
+    /// `Err(SensorError::Timeout)` if the timeout elapsed before the interrupt was asserted.
+    pub(crate) async fn wait_for_interrupt(
+        &mut self,
+        timeout: Option<Duration>,
+    ) -> Result<(), SensorError> {
+        self.sensor
+            .wait_for_ready(&mut self.interrupt, timeout)
+            .await
     }
 
     pub async fn reset_sensor(&mut self) {
         self.sensor.reset_sensor().await;
     }
 
+    /// Returns the level of the enable pin that powers the sensor on.
+    pub fn enable_polarity(&self) -> EnablePolarity {
+        self.sensor.polarity()
+    }
+
+    /// Returns the maximum number of bytes the HAL transfers in a single SPI transaction.
+    pub fn max_spi_transfer_size(&self) -> u16 {
+        self._hal.max_spi_transfer_size()
+    }
+
     /// Checks if a sensor is connected and responsive.
     ///
     /// Note that the sensor must be powered on before calling this function.
@@ -273,13 +1158,20 @@ where
         unsafe { acc_sensor_connected(self.id as acc_sensor_id_t) }
     }
 
-    /// Checks the status of the sensor.
+    /// Checks the status of the sensor, see [`log_status`](Self::log_status).
+    #[deprecated(note = "renamed to `log_status`, the SDK only reports the status in its log")]
+    pub fn check_status(&self) {
+        self.log_status()
+    }
+
+    /// Logs the status of the sensor.
     ///
     /// This function reads out the internal status from the sensor and can be used for
-    /// debugging purposes. The log is printed out through the log interface.
+    /// debugging purposes. The log is printed out through the log interface, the SDK does not
+    /// return the status in any other form.
     /// The sensor must be powered on before calling this function.
-    pub fn check_status(&self) {
-        self.sensor.check_status();
+    pub fn log_status(&self) {
+        self.sensor.log_status();
     }
 
     /// Get a mutable reference to the sensor
@@ -295,3 +1187,175 @@ pub fn rss_version() -> RssVersion {
     let version = unsafe { acc_version_get_hex() };
     RssVersion::new(version)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embassy_futures::block_on;
+    use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+    use embedded_hal_mock::eh1::MockError;
+
+    #[test]
+    fn spi_frequency_hint_scales_with_frame_size() {
+        let frame_rate = FrameRate::Limited(100.0);
+        let small = spi_frequency_for(4_000, frame_rate);
+        let large = spi_frequency_for(8_000, frame_rate);
+        assert_eq!(small, 6_400_000);
+        assert_eq!(large, 2 * small);
+    }
+
+    /// Buffer aligned to [`BUFFER_ALIGNMENT`] bytes
+    #[repr(align(4))]
+    struct Aligned([u8; 16]);
+
+    #[test]
+    fn misaligned_dma_buffer_is_rejected() {
+        let buf = Aligned([0; 16]);
+        assert!(check_dma_alignment(&buf.0).is_ok());
+        assert!(check_dma_alignment(&buf.0[4..]).is_ok());
+        assert!(matches!(
+            check_dma_alignment(&buf.0[1..]),
+            Err(SensorError::InvalidBuffer)
+        ));
+    }
+
+    #[test]
+    fn polled_interrupt_reads_the_pin_level() {
+        let mut interrupt = PinMock::new(&[
+            PinTransaction::get(State::High),
+            PinTransaction::get(State::Low),
+            PinTransaction::get(State::High)
+                .with_error(MockError::Io(std::io::ErrorKind::NotConnected)),
+        ]);
+        assert!(interrupt_asserted(&mut interrupt));
+        assert!(!interrupt_asserted(&mut interrupt));
+        assert!(!interrupt_asserted(&mut interrupt));
+        interrupt.done();
+    }
+
+    #[test]
+    fn polled_interrupt_can_still_be_awaited() {
+        let mut interrupt = PinMock::new(&[
+            PinTransaction::wait_for_state(State::High),
+            PinTransaction::get(State::High),
+        ]);
+        block_on(interrupt.wait_for_high()).unwrap();
+        assert!(interrupt_asserted(&mut interrupt));
+        interrupt.done();
+    }
+
+    fn sample(real: i16, imag: i16) -> acc_int16_complex_t {
+        acc_int16_complex_t { real, imag }
+    }
+
+    #[test]
+    fn selftest_reports_the_peak_loopback_amplitude() {
+        let frame = [sample(10, -20), sample(-350, 40), sample(120, 300)];
+        let report = selftest_report(&frame, false);
+        assert_eq!(report.peak_amplitude, 350);
+        assert!(report.passed);
+        assert!(!report.saturated);
+    }
+
+    #[test]
+    fn selftest_fails_on_weak_or_saturated_loopback() {
+        let weak = [sample(SELFTEST_MIN_AMPLITUDE as i16 - 1, 0)];
+        assert!(!selftest_report(&weak, false).passed);
+        let threshold = [sample(0, -(SELFTEST_MIN_AMPLITUDE as i16))];
+        assert!(selftest_report(&threshold, false).passed);
+        assert!(!selftest_report(&threshold, true).passed);
+        assert_eq!(selftest_report(&[], false).peak_amplitude, 0);
+        assert_eq!(
+            selftest_report(&[sample(i16::MIN, 0)], false).peak_amplitude,
+            32768
+        );
+    }
+
+    #[test]
+    fn calibration_is_reused_up_to_the_max_delta() {
+        assert!(calibration_reusable(25, 25, 0));
+        assert!(calibration_reusable(25, 40, 15));
+        assert!(calibration_reusable(40, 25, 15));
+        assert!(!calibration_reusable(25, 41, 15));
+        assert!(!calibration_reusable(41, 25, 15));
+    }
+
+    #[test]
+    fn calibration_reuse_handles_extreme_temperatures() {
+        assert!(calibration_reusable(i16::MIN, i16::MAX, u16::MAX));
+        assert!(!calibration_reusable(i16::MIN, i16::MAX, u16::MAX - 1));
+        assert!(!calibration_reusable(i16::MAX, i16::MIN, 15));
+        assert!(calibration_reusable(i16::MIN, i16::MIN, 0));
+    }
+
+    #[test]
+    fn spi_frequency_hint_is_bounded() {
+        assert_eq!(
+            spi_frequency_for(16, FrameRate::Limited(1.0)),
+            MIN_SPI_FREQUENCY
+        );
+        assert_eq!(
+            spi_frequency_for(16_380, FrameRate::Limited(1_000.0)),
+            MAX_SPI_FREQUENCY
+        );
+        assert_eq!(
+            spi_frequency_for(16, FrameRate::Unlimited),
+            MAX_SPI_FREQUENCY
+        );
+    }
+
+    #[test]
+    fn external_scratch_is_used_as_is() {
+        let buffer: &'static mut [u8] = Box::leak(vec![0u8; SCRATCH_SIZE + 8].into_boxed_slice());
+        let address = buffer.as_ptr();
+        let mut scratch = Scratch::external(buffer).unwrap();
+        assert_eq!(scratch.as_ptr(), address);
+        assert_eq!(scratch.len(), SCRATCH_SIZE + 8);
+        scratch[0] = 1;
+        assert_eq!(scratch[0], 1);
+    }
+
+    #[test]
+    fn short_external_scratch_is_rejected() {
+        let buffer: &'static mut [u8] = Box::leak(vec![0u8; SCRATCH_SIZE - 1].into_boxed_slice());
+        assert!(matches!(
+            Scratch::external(buffer),
+            Err(SensorError::BufferTooSmall)
+        ));
+    }
+
+    #[test]
+    fn drop_policy_decides_on_delayed_frames() {
+        for policy in [DropPolicy::Accept, DropPolicy::Drop, DropPolicy::Retry(2)] {
+            assert_eq!(policy.frame_action(false, 2), FrameAction::Return);
+        }
+        assert_eq!(
+            DropPolicy::Accept.frame_action(true, 0),
+            FrameAction::Return
+        );
+        assert_eq!(DropPolicy::Drop.frame_action(true, 0), FrameAction::Drop);
+        assert_eq!(
+            DropPolicy::Retry(2).frame_action(true, 2),
+            FrameAction::Retry
+        );
+        assert_eq!(
+            DropPolicy::Retry(2).frame_action(true, 1),
+            FrameAction::Retry
+        );
+        assert_eq!(
+            DropPolicy::Retry(2).frame_action(true, 0),
+            FrameAction::Return
+        );
+    }
+
+    #[test]
+    fn frame_buffer_must_hold_a_frame() {
+        assert!(matches!(
+            check_frame_buffer(99, 100),
+            Err(SensorError::BufferTooSmall)
+        ));
+        assert!(check_frame_buffer(100, 100).is_ok());
+        assert!(check_frame_buffer(128, 100).is_ok());
+        assert!(check_frame_buffer(0, 0).is_ok());
+    }
+}
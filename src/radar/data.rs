@@ -0,0 +1,91 @@
+/// Fixed-capacity container for the raw data of a measured frame.
+///
+/// Keeps track of how many bytes of its storage hold valid data.
+pub struct RadarData<const N: usize> {
+    data: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for RadarData<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> RadarData<N> {
+    /// Creates an empty container.
+    pub const fn new() -> Self {
+        Self {
+            data: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Returns the valid data.
+    pub fn data(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+
+    /// Returns the valid data mutably, e.g. for processing.
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.data[..self.len]
+    }
+
+    /// Returns the number of valid bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the container holds no valid data.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the capacity of the container in bytes.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Discards the valid data.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Discards the valid data and returns the whole storage, to be filled before calling
+    /// [`set_len`](Self::set_len).
+    pub(super) fn storage_mut(&mut self) -> &mut [u8] {
+        self.len = 0;
+        &mut self.data
+    }
+
+    /// Marks the first `len` bytes of the storage as valid.
+    pub(super) fn set_len(&mut self, len: usize) {
+        self.len = len.min(N);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storage_is_valid_only_once_filled() {
+        let mut data = RadarData::<8>::new();
+        data.storage_mut()[..4].copy_from_slice(&[1, 2, 3, 4]);
+        data.set_len(4);
+        assert_eq!(data.len(), 4);
+        assert_eq!(data.data(), &[1, 2, 3, 4]);
+
+        // An interrupted fill leaves no stale data behind
+        data.storage_mut()[0] = 9;
+        assert!(data.is_empty());
+        assert!(data.data().is_empty());
+    }
+
+    #[test]
+    fn length_is_bounded_by_capacity() {
+        let mut data = RadarData::<4>::new();
+        data.set_len(10);
+        assert_eq!(data.len(), data.capacity());
+    }
+}
@@ -0,0 +1,143 @@
+/// Fixed-capacity recorder keeping the last `K` frames of `FRAME` bytes each.
+///
+/// Frames are stored in a ring buffer, the oldest frame being overwritten once the recorder
+/// is full. No allocation is performed, making it suitable for on-device debugging.
+///
+/// A measurement can be written directly into the recorder, then committed once it succeeded:
+///
+/// ```ignore
+/// let mut recorder: FrameRecorder<4, 2560> = FrameRecorder::new();
+/// radar.measure(recorder.next_frame_mut()).await?;
+/// recorder.commit();
+/// ```
+pub struct FrameRecorder<const K: usize, const FRAME: usize> {
+    frames: [[u8; FRAME]; K],
+    next: usize,
+    len: usize,
+}
+
+impl<const K: usize, const FRAME: usize> Default for FrameRecorder<K, FRAME> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const K: usize, const FRAME: usize> FrameRecorder<K, FRAME> {
+    /// Creates an empty recorder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `K` is zero.
+    pub const fn new() -> Self {
+        assert!(
+            K > 0,
+            "FrameRecorder needs a capacity of at least one frame"
+        );
+        Self {
+            frames: [[0; FRAME]; K],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the next frame slot to write into, which holds the oldest frame if full.
+    ///
+    /// The slot is only counted as recorded once [`commit`](Self::commit) is called, so that a
+    /// failed write does not show up as the newest frame. Until then, the oldest frame may
+    /// have been partially overwritten.
+    pub fn next_frame_mut(&mut self) -> &mut [u8] {
+        &mut self.frames[self.next]
+    }
+
+    /// Records the frame written into the slot returned by
+    /// [`next_frame_mut`](Self::next_frame_mut), overwriting the oldest frame if full.
+    pub fn commit(&mut self) {
+        self.next = (self.next + 1) % K;
+        self.len = (self.len + 1).min(K);
+    }
+
+    /// Records a copy of `frame`, truncating it to `FRAME` bytes if longer.
+    pub fn push(&mut self, frame: &[u8]) {
+        let slot = self.next_frame_mut();
+        let len = frame.len().min(FRAME);
+        slot[..len].copy_from_slice(&frame[..len]);
+        slot[len..].fill(0);
+        self.commit();
+    }
+
+    /// Returns the recorded frames, from oldest to newest.
+    pub fn frames(&self) -> impl Iterator<Item = &[u8]> {
+        let start = (self.next + K - self.len) % K;
+        (0..self.len).map(move |i| &self.frames[(start + i) % K][..])
+    }
+
+    /// Returns the most recently recorded frame, if any.
+    pub fn latest(&self) -> Option<&[u8]> {
+        if self.len == 0 {
+            None
+        } else {
+            Some(&self.frames[(self.next + K - 1) % K])
+        }
+    }
+
+    /// Returns the number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no frame has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the maximum number of frames kept by the recorder.
+    pub fn capacity(&self) -> usize {
+        K
+    }
+
+    /// Discards all recorded frames.
+    pub fn clear(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_last_frames_from_oldest_to_newest() {
+        let mut recorder: FrameRecorder<3, 2> = FrameRecorder::new();
+        for i in 1..=4 {
+            recorder.push(&[i, i]);
+        }
+        assert_eq!(recorder.len(), 3);
+        let frames: Vec<&[u8]> = recorder.frames().collect();
+        assert_eq!(frames, [&[2, 2][..], &[3, 3], &[4, 4]]);
+        assert_eq!(recorder.latest(), Some(&[4, 4][..]));
+    }
+
+    #[test]
+    fn truncates_and_pads_frames() {
+        let mut recorder: FrameRecorder<2, 2> = FrameRecorder::new();
+        recorder.push(&[1, 2, 3]);
+        recorder.push(&[4]);
+        let frames: Vec<&[u8]> = recorder.frames().collect();
+        assert_eq!(frames, [&[1, 2][..], &[4, 0]]);
+    }
+
+    #[test]
+    fn only_committed_slots_are_recorded() {
+        let mut recorder: FrameRecorder<2, 1> = FrameRecorder::new();
+        recorder.next_frame_mut()[0] = 1;
+        assert!(recorder.is_empty());
+        assert_eq!(recorder.latest(), None);
+        recorder.commit();
+        recorder.push(&[2]);
+        recorder.next_frame_mut()[0] = 3;
+        assert_eq!(recorder.latest(), Some(&[2][..]));
+        recorder.clear();
+        assert!(recorder.is_empty());
+    }
+}
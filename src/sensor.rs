@@ -1,7 +1,9 @@
 use core::ffi::c_void;
 
 use core::ops::{Deref, DerefMut};
-use defmt::trace;
+use core::time::Duration;
+use defmt::{trace, warn};
+use embassy_futures::select::{select, Either};
 use embedded_hal::digital::OutputPin;
 use embedded_hal_async::delay::DelayNs;
 
@@ -11,6 +13,7 @@ use calibration::CalibrationResult;
 use error::SensorError;
 
 use crate::config::RadarConfig;
+use crate::radar::EnablePolarity;
 use a121_sys::*;
 
 pub mod calibration;
@@ -53,14 +56,120 @@ impl DerefMut for InnerSensor {
     }
 }
 
+/// Waits for `interrupt` to be asserted, giving up after `timeout`, measured with `dly`, if
+/// one is given.
+async fn wait_for_interrupt<SINT: Wait, DLY: DelayNs>(
+    interrupt: &mut SINT,
+    dly: &mut DLY,
+    timeout: Option<Duration>,
+) -> Result<(), SensorError> {
+    let Some(timeout) = timeout else {
+        interrupt
+            .wait_for_high()
+            .await
+            .expect("Failed to wait for interrupt");
+        return Ok(());
+    };
+    let timeout_us = u32::try_from(timeout.as_micros()).unwrap_or(u32::MAX);
+    match select(interrupt.wait_for_high(), dly.delay_us(timeout_us)).await {
+        Either::First(res) => {
+            res.expect("Failed to wait for interrupt");
+            Ok(())
+        }
+        Either::Second(()) => Err(SensorError::Timeout),
+    }
+}
+
+/// Waits for `interrupt` to be asserted and, if `debounce` is given, to still be asserted
+/// after that time, waiting for it to be asserted anew if it was a glitch.
+async fn wait_for_ready<SINT: Wait, DLY: DelayNs>(
+    interrupt: &mut SINT,
+    dly: &mut DLY,
+    timeout: Option<Duration>,
+    debounce: Option<Duration>,
+) -> Result<(), SensorError> {
+    wait_for_interrupt(interrupt, dly, timeout).await?;
+    if let Some(debounce) = debounce {
+        let debounce_us = u32::try_from(debounce.as_micros()).unwrap_or(u32::MAX);
+        dly.delay_us(debounce_us).await;
+        // Returns immediately if the interrupt is still asserted
+        wait_for_interrupt(interrupt, dly, timeout).await?;
+    }
+    Ok(())
+}
+
+/// Drives `pin` to the level that powers the sensor on if `enabled`, off otherwise.
+fn drive_enable_pin<ENABLE: OutputPin>(pin: &mut ENABLE, polarity: EnablePolarity, enabled: bool) {
+    if enabled == (polarity == EnablePolarity::ActiveHigh) {
+        pin.set_high().unwrap();
+    } else {
+        pin.set_low().unwrap();
+    }
+}
+
+/// Enable pin of the sensor, tracking whether the sensor must be reset before calibrating.
+struct SensorPower<ENABLE: OutputPin> {
+    pin: ENABLE,
+    polarity: EnablePolarity,
+    /// Whether the sensor must be reset before calibrating, false only from the moment it is
+    /// powered on until it is calibrated or powered off
+    needs_reset: bool,
+}
+
+impl<ENABLE: OutputPin> SensorPower<ENABLE> {
+    fn new(pin: ENABLE, polarity: EnablePolarity) -> Self {
+        Self {
+            pin,
+            polarity,
+            needs_reset: false,
+        }
+    }
+
+    /// Powers the sensor on, after which it can be calibrated without reset.
+    async fn enable<DLY: DelayNs>(&mut self, dly: &mut DLY) {
+        drive_enable_pin(&mut self.pin, self.polarity, true);
+        self.needs_reset = false;
+        dly.delay_ms(2).await;
+    }
+
+    /// Powers the sensor off, which loses its calibration and prepared configuration.
+    async fn disable<DLY: DelayNs>(&mut self, dly: &mut DLY) {
+        drive_enable_pin(&mut self.pin, self.polarity, false);
+        self.needs_reset = true;
+        dly.delay_ms(2).await;
+    }
+
+    async fn reset<DLY: DelayNs>(&mut self, dly: &mut DLY) {
+        self.disable(dly).await;
+        self.enable(dly).await;
+    }
+
+    /// Resets the sensor before a calibration if needed, or if `force` is set.
+    ///
+    /// # Returns
+    /// True if the sensor was reset.
+    async fn reset_before_calibration<DLY: DelayNs>(&mut self, dly: &mut DLY, force: bool) -> bool {
+        let reset = self.needs_reset || force;
+        if reset {
+            self.reset(dly).await;
+        }
+        self.needs_reset = true;
+        reset
+    }
+}
+
 pub(super) struct Sensor<ENABLE, DLY>
 where
     ENABLE: OutputPin,
     DLY: DelayNs,
 {
     inner: InnerSensor,
-    enable_pin: ENABLE,
+    power: SensorPower<ENABLE>,
     dly: DLY,
+    debounce: Option<Duration>,
+    /// Whether a measurement was started and its completion not yet observed, e.g. because
+    /// the future awaiting it was dropped
+    measurement_in_flight: bool,
 }
 
 impl<ENABLE, DLY> Sensor<ENABLE, DLY>
@@ -75,44 +184,109 @@ where
     ///
     /// # Arguments
     /// * `sensor_id` - The sensor ID to use for communication.
+    /// * `polarity` - The level of the enable pin that powers the sensor on.
     ///
     /// # Returns
     /// `Some(Sensor)` if the sensor instance was successfully created, `None` otherwise.
-    pub fn new(sensor_id: u32, enable_pin: ENABLE, delay: DLY) -> Option<Self> {
+    pub fn new(
+        sensor_id: u32,
+        enable_pin: ENABLE,
+        delay: DLY,
+        polarity: EnablePolarity,
+    ) -> Option<Self> {
         trace!("Creating sensor {}", sensor_id);
         let inner = InnerSensor::new(sensor_id)?;
         Some(Self {
             inner,
-            enable_pin,
+            power: SensorPower::new(enable_pin, polarity),
             dly: delay,
+            debounce: None,
+            measurement_in_flight: false,
         })
     }
 
     pub async fn reset_sensor(&mut self) {
-        self.disable_sensor().await;
-        self.enable_sensor().await;
+        self.power.reset(&mut self.dly).await;
+        self.measurement_in_flight = false;
     }
 
     pub async fn enable_sensor(&mut self) {
-        self.enable_pin.set_high().unwrap();
-        self.dly.delay_ms(2).await;
+        self.power.enable(&mut self.dly).await;
     }
 
+    /// Powers the sensor off, aborting any pending measurement. The next calibration resets
+    /// the sensor, even with [`calibrate_no_reset`](Self::calibrate_no_reset).
     pub async fn disable_sensor(&mut self) {
-        self.enable_pin.set_low().unwrap();
-        self.dly.delay_ms(2).await;
+        self.power.disable(&mut self.dly).await;
+        self.measurement_in_flight = false;
+    }
+
+    /// Returns the level of the enable pin that powers the sensor on.
+    pub fn polarity(&self) -> EnablePolarity {
+        self.power.polarity
     }
 
-    /// Calibrates the sensor asynchronously.
-    pub async fn calibrate<SINT: Wait>(
+    /// Waits for the sensor interrupt, giving up after `timeout` if one is given.
+    ///
+    /// # Returns
+    /// `Ok(())` once the interrupt is asserted, `Err(SensorError::Timeout)` if the timeout
+    /// elapsed first.
+    pub async fn wait_for_interrupt<SINT: Wait>(
+        &mut self,
+        interrupt: &mut SINT,
+        timeout: Option<Duration>,
+    ) -> Result<(), SensorError> {
+        wait_for_interrupt(interrupt, &mut self.dly, timeout).await
+    }
+
+    /// Sets the time the interrupt must stay asserted before the sensor is considered ready.
+    /// `None` disables the debounce.
+    pub fn set_debounce(&mut self, debounce: Option<Duration>) {
+        self.debounce = debounce;
+    }
+
+    /// Returns the interrupt debounce time, if any.
+    pub fn debounce(&self) -> Option<Duration> {
+        self.debounce
+    }
+
+    /// Waits until the sensor is ready, filtering out spurious interrupt assertions.
+    ///
+    /// After the interrupt is asserted, and if a debounce time is set, waits for that time and
+    /// checks the interrupt again, waiting for it to be asserted anew if it was a glitch.
+    ///
+    /// # Returns
+    /// `Ok(())` once the sensor is ready, `Err(SensorError::Timeout)` if the timeout elapsed
+    /// first.
+    pub async fn wait_for_ready<SINT: Wait>(
+        &mut self,
+        interrupt: &mut SINT,
+        timeout: Option<Duration>,
+    ) -> Result<(), SensorError> {
+        wait_for_ready(interrupt, &mut self.dly, timeout, self.debounce).await
+    }
+
+    /// Calibrates the sensor asynchronously, without resetting it first if it was just powered
+    /// on and never calibrated.
+    ///
+    /// If `timeout` is given, each wait for the sensor interrupt is bounded by it.
+    pub async fn calibrate_no_reset<SINT: Wait>(
         &mut self,
         interrupt: &mut SINT,
         buffer: &mut [u8],
+        timeout: Option<Duration>,
     ) -> Result<CalibrationResult, SensorError> {
         let mut calibration_complete: bool = false;
         let mut calibration_result = CalibrationResult::new();
 
-        self.reset_sensor().await;
+        // Powering the sensor off also aborts a measurement left pending
+        if self
+            .power
+            .reset_before_calibration(&mut self.dly, self.measurement_in_flight)
+            .await
+        {
+            self.measurement_in_flight = false;
+        }
 
         loop {
             let calibration_attempt = unsafe {
@@ -136,12 +310,10 @@ where
             }
 
             // Wait for the interrupt signal asynchronously
-            interrupt
-                .wait_for_high()
-                .await
-                .expect("Failed to wait for interrupt");
+            self.wait_for_ready(interrupt, timeout).await?;
         }
 
+        calibration_result.refresh_temperature()?;
         Ok(calibration_result)
     }
 
@@ -185,6 +357,7 @@ where
         cal_result: &mut CalibrationResult,
         buffer: &mut [u8],
     ) -> Result<(), SensorError> {
+        self.check_idle()?;
         let ret;
         unsafe {
             ret = acc_sensor_prepare(
@@ -203,7 +376,7 @@ where
         }
     }
 
-    pub fn check_status(&self) {
+    pub fn log_status(&self) {
         unsafe {
             acc_sensor_status(self.inner.deref());
         }
@@ -216,6 +389,7 @@ where
     /// # Returns
     /// `Ok(())` if preparation was successful, `Err(SensorHibernationError)` otherwise.
     pub fn hibernate_on(&mut self) -> Result<(), SensorError> {
+        self.check_idle()?;
         let ret_status: bool;
         unsafe {
             ret_status = acc_sensor_hibernate_on(self.inner.deref_mut());
@@ -264,6 +438,14 @@ where
     /// # Returns
     ///
     /// `Ok(())` if the measurement was successfully started, `Err(SensorError)` otherwise.
+    /// If `timeout` is given and the sensor interrupt is not asserted in time,
+    /// `Err(SensorError::Timeout)` is returned.
+    ///
+    /// # Cancellation
+    ///
+    /// If the returned future is dropped before the measurement completes, the measurement is
+    /// left pending and must be read out with [`drain`](Self::drain) before the sensor is used
+    /// again. Until then, this function returns `Err(SensorError::MeasurementInFlight)`.
     ///
     /// # Example
     ///
@@ -276,18 +458,21 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn measure<SINT: Wait>(&mut self, mut interrupt: SINT) -> Result<(), SensorError> {
+    pub async fn measure<SINT: Wait>(
+        &mut self,
+        interrupt: &mut SINT,
+        timeout: Option<Duration>,
+    ) -> Result<(), SensorError> {
+        self.check_idle()?;
         // Implementation to start the radar measurement
         let success = unsafe { acc_sensor_measure(self.inner.deref_mut()) };
-        if success {
-            interrupt
-                .wait_for_high()
-                .await
-                .expect("Failed to wait for interrupt");
-            Ok(())
-        } else {
-            Err(SensorError::MeasurementError)
+        if !success {
+            return Err(SensorError::MeasurementError);
         }
+        self.measurement_in_flight = true;
+        self.wait_for_ready(interrupt, timeout).await?;
+        self.measurement_in_flight = false;
+        Ok(())
     }
 
     /// Reads out radar data from the sensor.
@@ -336,7 +521,264 @@ where
         }
     }
 
+    /// Returns true if a measurement was started and never read out, because the future
+    /// awaiting it was dropped.
+    pub fn measurement_in_flight(&self) -> bool {
+        self.measurement_in_flight
+    }
+
+    /// Returns `Err(SensorError::MeasurementInFlight)` if a measurement is pending, see
+    /// [`drain`](Self::drain).
+    pub fn check_idle(&self) -> Result<(), SensorError> {
+        if self.measurement_in_flight {
+            Err(SensorError::MeasurementInFlight)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Waits for a measurement left pending by a cancelled [`measure`](Self::measure) and
+    /// reads it out into `buffer`, discarding it. Does nothing if no measurement is pending.
+    ///
+    /// # Arguments
+    /// * `buffer` - A buffer large enough for a frame, its content is overwritten.
+    pub async fn drain<SINT: Wait>(
+        &mut self,
+        interrupt: &mut SINT,
+        buffer: &mut [u8],
+        timeout: Option<Duration>,
+    ) -> Result<(), SensorError> {
+        if !self.measurement_in_flight {
+            return Ok(());
+        }
+        warn!("Discarding measurement left pending by a cancelled measure");
+        self.wait_for_ready(interrupt, timeout).await?;
+        self.measurement_in_flight = false;
+        self.read(buffer)
+    }
+
     pub unsafe fn inner(&self) -> *mut acc_sensor_t {
         self.inner.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+    use embassy_futures::block_on;
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+
+    /// Interrupt pin that is never asserted
+    struct NeverAsserted;
+
+    impl embedded_hal::digital::ErrorType for NeverAsserted {
+        type Error = Infallible;
+    }
+
+    impl Wait for NeverAsserted {
+        async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+            core::future::pending().await
+        }
+    }
+
+    /// Interrupt pin asserted once by a glitch, then never again
+    struct Glitch {
+        asserted: bool,
+    }
+
+    impl embedded_hal::digital::ErrorType for Glitch {
+        type Error = Infallible;
+    }
+
+    impl Wait for Glitch {
+        async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+            if core::mem::take(&mut self.asserted) {
+                return Ok(());
+            }
+            core::future::pending().await
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+            core::future::pending().await
+        }
+    }
+
+    #[test]
+    fn interrupt_wait_times_out() {
+        let result = block_on(wait_for_interrupt(
+            &mut NeverAsserted,
+            &mut NoopDelay::new(),
+            Some(Duration::from_millis(10)),
+        ));
+        assert!(matches!(result, Err(SensorError::Timeout)));
+    }
+
+    #[test]
+    fn asserted_interrupt_does_not_time_out() {
+        let mut interrupt = PinMock::new(&[PinTransaction::wait_for_state(State::High)]);
+        let result = block_on(wait_for_interrupt(
+            &mut interrupt,
+            &mut NoopDelay::new(),
+            Some(Duration::from_millis(10)),
+        ));
+        assert!(result.is_ok());
+        interrupt.done();
+    }
+
+    #[test]
+    fn debounce_waits_again_after_a_glitch() {
+        // The second wait only completes once the interrupt is asserted anew
+        let mut interrupt = PinMock::new(&[
+            PinTransaction::wait_for_state(State::High),
+            PinTransaction::wait_for_state(State::High),
+        ]);
+        let result = block_on(wait_for_ready(
+            &mut interrupt,
+            &mut NoopDelay::new(),
+            None,
+            Some(Duration::from_micros(50)),
+        ));
+        assert!(result.is_ok());
+        interrupt.done();
+    }
+
+    #[test]
+    fn no_debounce_waits_once() {
+        let mut interrupt = PinMock::new(&[PinTransaction::wait_for_state(State::High)]);
+        let result = block_on(wait_for_ready(
+            &mut interrupt,
+            &mut NoopDelay::new(),
+            None,
+            None,
+        ));
+        assert!(result.is_ok());
+        interrupt.done();
+    }
+
+    #[test]
+    fn glitch_without_reassertion_times_out() {
+        let result = block_on(wait_for_ready(
+            &mut Glitch { asserted: true },
+            &mut NoopDelay::new(),
+            Some(Duration::from_millis(10)),
+            Some(Duration::from_micros(50)),
+        ));
+        assert!(matches!(result, Err(SensorError::Timeout)));
+    }
+
+    #[test]
+    fn active_high_enable_pin() {
+        let mut pin = PinMock::new(&[
+            PinTransaction::set(State::High),
+            PinTransaction::set(State::Low),
+        ]);
+        drive_enable_pin(&mut pin, EnablePolarity::ActiveHigh, true);
+        drive_enable_pin(&mut pin, EnablePolarity::ActiveHigh, false);
+        pin.done();
+    }
+
+    #[test]
+    fn active_low_enable_pin() {
+        let mut pin = PinMock::new(&[
+            PinTransaction::set(State::Low),
+            PinTransaction::set(State::High),
+        ]);
+        drive_enable_pin(&mut pin, EnablePolarity::ActiveLow, true);
+        drive_enable_pin(&mut pin, EnablePolarity::ActiveLow, false);
+        pin.done();
+    }
+
+    #[test]
+    fn first_calibration_does_not_toggle_the_enable_pin() {
+        let mut pin = PinMock::new(&[]);
+        let mut power = SensorPower::new(pin.clone(), EnablePolarity::ActiveHigh);
+        assert!(!block_on(
+            power.reset_before_calibration(&mut NoopDelay::new(), false)
+        ));
+        pin.done();
+    }
+
+    #[test]
+    fn calibration_resets_after_a_calibration_or_power_off() {
+        let mut pin = PinMock::new(&[
+            PinTransaction::set(State::Low),
+            PinTransaction::set(State::High),
+            PinTransaction::set(State::Low),
+            PinTransaction::set(State::Low),
+            PinTransaction::set(State::High),
+        ]);
+        let mut power = SensorPower::new(pin.clone(), EnablePolarity::ActiveHigh);
+        let mut dly = NoopDelay::new();
+        block_on(async {
+            assert!(!power.reset_before_calibration(&mut dly, false).await);
+            assert!(power.reset_before_calibration(&mut dly, false).await);
+            power.disable(&mut dly).await;
+            assert!(power.reset_before_calibration(&mut dly, false).await);
+        });
+        pin.done();
+    }
+
+    #[test]
+    fn pending_measurement_forces_a_reset() {
+        let mut pin = PinMock::new(&[
+            PinTransaction::set(State::High),
+            PinTransaction::set(State::Low),
+        ]);
+        let mut power = SensorPower::new(pin.clone(), EnablePolarity::ActiveLow);
+        assert!(block_on(
+            power.reset_before_calibration(&mut NoopDelay::new(), true)
+        ));
+        pin.done();
+    }
+
+    #[test]
+    fn guarded_reset_orders_the_enable_pin() {
+        // Power off, power on for the guarded scope, then power off when released
+        let mut pin = PinMock::new(&[
+            PinTransaction::set(State::Low),
+            PinTransaction::set(State::High),
+            PinTransaction::set(State::Low),
+        ]);
+        let mut power = SensorPower::new(pin.clone(), EnablePolarity::ActiveHigh);
+        let mut dly = NoopDelay::new();
+        block_on(async {
+            power.disable(&mut dly).await;
+            assert!(power.needs_reset);
+            power.enable(&mut dly).await;
+            assert!(!power.needs_reset);
+            power.disable(&mut dly).await;
+            assert!(power.needs_reset);
+        });
+        pin.done();
+    }
+}
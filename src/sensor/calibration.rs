@@ -3,11 +3,25 @@ use a121_sys::{
     acc_cal_info_t, acc_cal_result_t, acc_sensor_get_cal_info, acc_sensor_validate_calibration,
 };
 
+/// Information extracted from a calibration result.
+///
+/// The SDK currently only reports the temperature at which the calibration was performed.
 pub struct CalibrationInfo {
     inner: acc_cal_info_t,
 }
 
+impl defmt::Format for CalibrationInfo {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "CalibrationInfo {{ temperature: {} }}",
+            self.temperature()
+        )
+    }
+}
+
 impl CalibrationInfo {
+    /// Returns the temperature, in degrees Celsius, at which the calibration was performed.
     pub fn temperature(&self) -> i16 {
         self.inner.temperature
     }
@@ -20,9 +34,11 @@ impl CalibrationInfo {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CalibrationResult {
     inner: acc_cal_result_t,
+    /// Temperature read when the calibration completed, 0 for a default result
+    temperature: i16,
 }
 
 impl defmt::Format for CalibrationResult {
@@ -41,6 +57,9 @@ impl CalibrationResult {
     }
 
     /// Returns a mutable pointer to the inner `acc_cal_result_t` struct.
+    ///
+    /// After writing a result through the pointer, call
+    /// [`refresh_temperature`](Self::refresh_temperature) to update the cached temperature.
     /// # Safety
     /// This function is unsafe because it returns a raw pointer.
     pub unsafe fn mut_ptr(&mut self) -> *mut acc_cal_result_t {
@@ -65,29 +84,66 @@ impl CalibrationResult {
         }
     }
 
-    pub fn temperature(&self) -> Result<i16, SensorError> {
+    /// Retrieves the information contained in the calibration result.
+    ///
+    /// # Returns
+    /// `Ok(CalibrationInfo)` if the information could be read, `Err(SensorError::CalibrationInfo)`
+    /// otherwise.
+    pub fn info(&self) -> Result<CalibrationInfo, SensorError> {
         let mut calibration_info = CalibrationInfo::default();
         let res = unsafe { acc_sensor_get_cal_info(self.ptr(), calibration_info.mut_ptr()) };
         if res {
-            Ok(calibration_info.temperature())
+            Ok(calibration_info)
         } else {
             Err(SensorError::CalibrationInfo)
         }
     }
+
+    pub fn temperature(&self) -> Result<i16, SensorError> {
+        self.info().map(|info| info.temperature())
+    }
+
+    /// Returns the temperature at which the calibration was performed, without calling the SDK.
+    ///
+    /// The temperature is read once when the calibration completes, and is 0 for a default
+    /// result, which is not a valid calibration.
+    pub fn cached_temperature(&self) -> i16 {
+        self.temperature
+    }
+
+    /// Reads the temperature of the result from the SDK into the cache used by
+    /// [`cached_temperature`](Self::cached_temperature).
+    ///
+    /// # Returns
+    /// `Err(SensorError::CalibrationInfo)` if the temperature could not be read, in which case
+    /// the cached temperature is left unchanged.
+    pub fn refresh_temperature(&mut self) -> Result<(), SensorError> {
+        self.temperature = self.temperature()?;
+        Ok(())
+    }
 }
 
-impl From<CalibrationResult> for CalibrationInfo {
-    fn from(calibration_result: CalibrationResult) -> Self {
+impl From<&CalibrationResult> for CalibrationInfo {
+    fn from(calibration_result: &CalibrationResult) -> Self {
         let mut calibration_info = CalibrationInfo::default();
         unsafe { acc_sensor_get_cal_info(calibration_result.ptr(), calibration_info.mut_ptr()) };
         calibration_info
     }
 }
 
+impl From<CalibrationResult> for CalibrationInfo {
+    fn from(calibration_result: CalibrationResult) -> Self {
+        CalibrationInfo::from(&calibration_result)
+    }
+}
+
 impl Default for CalibrationResult {
     fn default() -> Self {
         let inner = acc_cal_result_t { data: [0; 48] };
-        Self { inner }
+        Self {
+            inner,
+            temperature: 0,
+        }
     }
 }
 
@@ -97,3 +153,33 @@ impl Default for CalibrationInfo {
         Self { inner }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_info_fields() {
+        let info = CalibrationInfo::default();
+        assert_eq!(info.temperature(), 0);
+    }
+
+    #[test]
+    fn info_fields_read_from_the_sdk_struct() {
+        let mut info = CalibrationInfo::default();
+        unsafe { (*info.mut_ptr()).temperature = -12 };
+        assert_eq!(info.temperature(), -12);
+    }
+
+    #[test]
+    fn cached_temperature_survives_writes_and_clones() {
+        let mut result = CalibrationResult::default();
+        assert_eq!(result.cached_temperature(), 0);
+
+        result.temperature = 31;
+        unsafe { (*result.mut_ptr()).data[0] = 1 };
+        let copy = result.clone();
+        assert_eq!(result.cached_temperature(), 31);
+        assert_eq!(copy.cached_temperature(), 31);
+    }
+}
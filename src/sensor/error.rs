@@ -12,4 +12,16 @@ pub enum SensorError {
     ResultNotAvailable,
     ProcessingFailed,
     BufferTooSmall,
+    Timeout,
+    InitFailed,
+    TimingInfeasible,
+    InvalidBuffer,
+    HighSpeedIncompatible,
+    FrameDropped,
+    PhaseEnhancementDisabled,
+    StaleBuffer,
+    MeasurementInFlight,
+    PersistentSaturation,
+    PersistentFrameDelay,
+    PersistentCalibrationNeeded,
 }